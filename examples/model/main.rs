@@ -2,6 +2,7 @@
 mod common;
 
 use bytemuck::{Pod, Zeroable};
+use m3d::VertexColor;
 use model3d as m3d;
 use nalgebra_glm as glm;
 use std::{borrow::Cow, f32::consts::FRAC_PI_4};
@@ -34,32 +35,53 @@ impl common::Example for CubeExample {
         surface: &wgpu::Surface,
         queue: &wgpu::Queue,
     ) {
-        let m3d_obj = m3d::Obj::load_from_file("model3d/models/cube_usemtl.m3d", None).unwrap();
+        let m3d_obj = m3d::Model::load(
+            std::path::Path::new("examples/model/lantea.m3d"),
+            m3d::LoadOptions::new(),
+        )
+        .unwrap()
+        .model;
 
-        let vertices = m3d_obj
-            .faces()
+        // lantea.m3d is made of voxels only, they are meshed into faces first.
+        let voxels = m3d_obj.mesh_voxels();
+        let (m3d_vertices, m3d_faces) = if m3d_obj.faces().is_empty() {
+            (&voxels.vertices[..], &voxels.faces[..])
+        } else {
+            (m3d_obj.vertices(), m3d_obj.faces())
+        };
+
+        let mut vertices = m3d_faces
             .iter()
             .flat_map(|f| {
                 let mut vertices: [Vertex; 3] = [Vertex::default(); 3];
                 for idx in 0..3 {
-                    let v = m3d_obj.vertices()[f.vertex[idx as usize] as usize];
-                    let vn = m3d_obj.vertices()[f.normal[idx as usize] as usize];
-
-                    let [r, g, b, a]: [f32; 4] = [
-                        ((v.color >> 00) & 0xff) as f32 / (u8::MAX as f32),
-                        ((v.color >> 08) & 0xff) as f32 / (u8::MAX as f32),
-                        ((v.color >> 16) & 0xff) as f32 / (u8::MAX as f32),
-                        ((v.color >> 24) & 0xff) as f32 / (u8::MAX as f32),
-                    ];
+                    let v = m3d_vertices[f.vertex[idx] as usize];
+                    let vn = m3d_vertices[f.normal[idx] as usize];
 
                     vertices[idx].position = [v.x, v.y, v.z];
-                    vertices[idx].color = [r, g, b, a];
+                    vertices[idx].color = v.color().to_srgb();
                     vertices[idx].normal = [vn.x, vn.y, vn.z];
                 }
                 vertices
             })
             .collect::<Vec<_>>();
 
+        // Voxels are one unit each, fit the model into the unit cube.
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for v in &vertices {
+            for (k, c) in v.position.into_iter().enumerate() {
+                min[k] = min[k].min(c);
+                max[k] = max[k].max(c);
+            }
+        }
+        let size = (0..3).map(|k| max[k] - min[k]).fold(f32::EPSILON, f32::max);
+        for v in &mut vertices {
+            for (c, min) in v.position.iter_mut().zip(min) {
+                *c = (*c - min) / size;
+            }
+        }
+
         let window_size = window.inner_size();
 
         let perspective = glm::perspective_fov_rh(
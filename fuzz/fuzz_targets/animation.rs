@@ -0,0 +1,86 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use model3d::*;
+use std::collections::HashMap;
+
+fuzz_target!(|data: &[u8]| {
+    let assets = HashMap::<String, Vec<u8>>::new();
+    let options = LoadOptions::new()
+        .resolver(&assets)
+        .limits(Limits::default());
+    let model = match Model::load(data, options) {
+        Ok(report) => report.model,
+        Err(_) => return,
+    };
+
+    walk(&model);
+    // Sampling is the SDK's, the native build only has the decoded actions.
+    #[cfg(not(feature = "pure-rust"))]
+    sample(&model);
+});
+
+// Whatever the BONE and ACTN chunks held, the views only point at bones and vertices that exist.
+fn walk(model: &Model) {
+    let (bones, vertices) = (model.bones(), model.vertices());
+    let optional = |id: Index, len: usize| id == Index::MAX || (id as usize) < len;
+    for bone in bones {
+        assert!(optional(bone.parent(), bones.len()));
+        let _ = (
+            bone.name(),
+            &vertices[bone.position() as usize],
+            &vertices[bone.orientation() as usize],
+        );
+        for weight in bone.weights() {
+            assert!((weight.vertexid as usize) < vertices.len());
+        }
+    }
+    for skin in model.skins() {
+        assert!(skin.boneid.iter().all(|&id| optional(id, bones.len())));
+    }
+
+    for action in model.actions() {
+        let _ = (action.name(), action.duraction_msec());
+        for frame in action.frames() {
+            let _ = frame.msec();
+            for transform in frame.transforms() {
+                let _ = (
+                    &bones[transform.boneid as usize],
+                    &vertices[transform.pos as usize],
+                    &vertices[transform.ori as usize],
+                );
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "pure-rust"))]
+fn sample(model: &Model) {
+    // One past the last action, and `M3D_UNDEF` for the bind pose.
+    let count = model.actions().len() as Index;
+    for action_id in (0..=count).chain([Index::MAX]) {
+        let action = model.actions().get(action_id as usize);
+        let frames = action.map_or(1, |a| a.frames().len() as Index + 1);
+
+        let mut skeleton = None;
+        for frame_id in 0..frames {
+            let transforms = model.frame(action_id, frame_id, skeleton.as_deref());
+            skeleton = transforms.or(skeleton);
+        }
+        // Too short a skeleton is refused rather than written past.
+        if !model.bones().is_empty() {
+            assert!(model.frame(action_id, 0, Some(&[])).is_none());
+        }
+
+        let duration = action.map_or(0, |a| a.duraction_msec());
+        for msec in [
+            0,
+            duration / 3,
+            duration / 2,
+            duration,
+            duration.saturating_add(1),
+        ] {
+            let _ = model.pose(action_id, msec);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use model3d::*;
+
+// `ModelData` holds C structs, so the input mirrors it with plain fields. Indices are left
+// unchecked on purpose, `from_data` is what has to refuse them.
+#[derive(Debug, Arbitrary)]
+struct Input {
+    name: String,
+    scale: f32,
+    color_maps: Vec<u32>,
+    texture_maps: Vec<[f32; 2]>,
+    textures: Vec<TextureInput>,
+    bones: Vec<BoneInput>,
+    vertices: Vec<VertexInput>,
+    skins: Vec<([u32; 4], [f32; 4])>,
+    materials: Vec<MaterialInput>,
+    faces: Vec<FaceInput>,
+    voxel_types: Vec<VoxelTypeInput>,
+    voxels: Vec<VoxelBlockInput>,
+    shapes: Vec<ShapeInput>,
+    labels: Vec<LabelInput>,
+    actions: Vec<ActionInput>,
+    inlined: Vec<(String, Vec<u8>)>,
+    extra: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct TextureInput {
+    name: String,
+    width: u16,
+    height: u16,
+    format: u8,
+    pixels: Vec<u8>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct BoneInput {
+    parent: u32,
+    name: String,
+    position: u32,
+    orientation: u32,
+    weights: Vec<(u32, f32)>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct VertexInput {
+    pos: [f32; 4],
+    color: u32,
+    skinid: u32,
+}
+
+#[derive(Debug, Arbitrary)]
+struct MaterialInput {
+    name: String,
+    props: Vec<(u8, u32)>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct FaceInput {
+    materialid: u32,
+    vertex: [u32; 3],
+    normal: [u32; 3],
+    texcoord: [u32; 3],
+}
+
+#[derive(Debug, Arbitrary)]
+struct VoxelTypeInput {
+    name: String,
+    shape: u16,
+    material_id: u32,
+    color: u32,
+    skin_id: u32,
+    items: Vec<(u16, String)>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct VoxelBlockInput {
+    name: String,
+    pos: [i32; 3],
+    size: [u32; 3],
+    data: Vec<u16>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct ShapeInput {
+    name: String,
+    group: u32,
+    commands: Vec<(u16, Vec<u32>)>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct LabelInput {
+    name: String,
+    text: String,
+    color: u32,
+    vertex_id: u32,
+}
+
+#[derive(Debug, Arbitrary)]
+struct ActionInput {
+    name: String,
+    duration_msec: u32,
+    frames: Vec<(u32, Vec<[u32; 3]>)>,
+}
+
+impl Input {
+    fn into_data(self) -> ModelData {
+        let mut data = ModelData::default();
+        data.name = self.name;
+        data.scale = self.scale;
+        data.color_maps = self.color_maps;
+        data.texture_maps = (self.texture_maps.into_iter())
+            .map(|[u, v]| TextureMapIndex { u, v })
+            .collect();
+        data.textures = (self.textures.into_iter())
+            .map(|t| TextureData {
+                name: t.name,
+                width: t.width,
+                height: t.height,
+                format: TextureFormat::from(t.format),
+                pixels: t.pixels,
+            })
+            .collect();
+        data.bones = (self.bones.into_iter())
+            .map(|b| BoneData {
+                parent: b.parent,
+                name: b.name,
+                position: b.position,
+                orientation: b.orientation,
+                weights: (b.weights.into_iter())
+                    .map(|(vertexid, weight)| Weight { vertexid, weight })
+                    .collect(),
+                mat4: [0.0; 16],
+            })
+            .collect();
+        data.vertices = (self.vertices.into_iter())
+            .map(|v| Vertex {
+                x: v.pos[0],
+                y: v.pos[1],
+                z: v.pos[2],
+                w: v.pos[3],
+                color: v.color,
+                skinid: v.skinid,
+            })
+            .collect();
+        data.skins = (self.skins.into_iter())
+            .map(|(boneid, weight)| Skin { boneid, weight })
+            .collect();
+        data.materials = (self.materials.into_iter())
+            .map(|m| MaterialData {
+                name: m.name,
+                props: (m.props.into_iter())
+                    .map(|(type_, num)| {
+                        let mut prop = MaterialProp {
+                            type_,
+                            value: unsafe { std::mem::zeroed() },
+                        };
+                        prop.value.num = num;
+                        prop
+                    })
+                    .collect(),
+            })
+            .collect();
+        data.faces = (self.faces.into_iter())
+            .map(|f| Face {
+                materialid: f.materialid,
+                vertex: f.vertex,
+                normal: f.normal,
+                texcoord: f.texcoord,
+            })
+            .collect();
+        data.voxel_types = (self.voxel_types.into_iter())
+            .map(|t| VoxelTypeData {
+                name: t.name,
+                rotation: 0,
+                shape: t.shape,
+                material_id: t.material_id,
+                color: t.color,
+                skin_id: t.skin_id,
+                items: (t.items.into_iter())
+                    .map(|(count, name)| VoxelItemData { count, name })
+                    .collect(),
+            })
+            .collect();
+        data.voxels = (self.voxels.into_iter())
+            .map(|b| VoxelBlockData {
+                name: b.name,
+                x: b.pos[0],
+                y: b.pos[1],
+                z: b.pos[2],
+                width: b.size[0],
+                height: b.size[1],
+                depth: b.size[2],
+                uncertain: 0,
+                group: 0,
+                data: b.data,
+            })
+            .collect();
+        data.shapes = (self.shapes.into_iter())
+            .map(|s| ShapeData {
+                name: s.name,
+                group: s.group,
+                commands: (s.commands.into_iter())
+                    .map(|(kind, args)| ShapeCommandData { kind, args })
+                    .collect(),
+            })
+            .collect();
+        data.labels = (self.labels.into_iter())
+            .map(|l| LabelData {
+                name: l.name,
+                lang: String::new(),
+                text: l.text,
+                color: l.color,
+                vertex_id: l.vertex_id,
+            })
+            .collect();
+        data.actions = (self.actions.into_iter())
+            .map(|a| ActionData {
+                name: a.name,
+                duration_msec: a.duration_msec,
+                frames: (a.frames.into_iter())
+                    .map(|(msec, transforms)| FrameData {
+                        msec,
+                        transforms: (transforms.into_iter())
+                            .map(|[boneid, pos, ori]| Transform { boneid, pos, ori })
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        data.inlined = (self.inlined.into_iter())
+            .map(|(name, data)| InlinedData { name, data })
+            .collect();
+        data.extra = self.extra;
+        data
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let data = input.into_data();
+    let model = match Model::from_data(&data) {
+        Ok(model) => model,
+        Err(_) => return,
+    };
+
+    // Every view of the exported model reads the arrays `from_data` laid out.
+    for block in model.voxels() {
+        let [w, h, d] = block.size();
+        assert_eq!(block.data().len(), w as usize * h as usize * d as usize);
+        let _ = (block.name(), block.get(w.saturating_sub(1), 0, 0));
+    }
+    for voxel_type in model.voxel_types() {
+        let _ = (voxel_type.name(), voxel_type.items());
+    }
+    let _ = model.mesh_voxels();
+
+    // What was exported imports back unchanged, and exports again.
+    let again = model.to_data();
+    assert_eq!(again.voxels, data.voxels);
+    assert_eq!(again.shapes, data.shapes);
+    assert_eq!(again.extra, data.extra);
+    Model::from_data(&again).expect("imported data converts back");
+});
@@ -0,0 +1,41 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use model3d::*;
+use std::collections::HashMap;
+
+fuzz_target!(|data: &[u8]| {
+    // Nothing outside the input gets resolved, and allocations stay bounded.
+    let assets = HashMap::<String, Vec<u8>>::new();
+    let options = LoadOptions::new()
+        .resolver(&assets)
+        .limits(Limits::default());
+    let model = match Model::load(data, options) {
+        Ok(report) => report.model,
+        Err(_) => return,
+    };
+
+    // Every view builds slices from the model's counts.
+    let _ = (model.name(), model.license(), model.author(), model.desc());
+    let _ = (model.color_maps(), model.texture_maps(), model.vertices());
+    let _ = (model.skins(), model.faces(), model.inlined().data());
+    for texture in model.textures() {
+        let _ = (texture.name(), texture.data());
+    }
+    for bone in model.bones() {
+        let _ = (bone.name(), bone.weights());
+    }
+    for material in model.materials() {
+        let _ = (material.name(), material.props());
+    }
+    for action in model.actions() {
+        let _ = action.name();
+        for frame in action.frames() {
+            let _ = frame.transforms();
+        }
+    }
+    for inlined in model.inlined_textures() {
+        let _ = (inlined.name(), inlined.data());
+    }
+    let _ = model.to_data();
+});
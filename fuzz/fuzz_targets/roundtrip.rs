@@ -0,0 +1,34 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use model3d::*;
+use std::collections::HashMap;
+
+fuzz_target!(|data: &[u8]| {
+    let assets = HashMap::<String, Vec<u8>>::new();
+    let options = || {
+        LoadOptions::new()
+            .resolver(&assets)
+            .limits(Limits::default())
+    };
+    let model = match Model::load(data, options()) {
+        Ok(report) => report.model,
+        Err(_) => return,
+    };
+
+    // Whatever loads has to save, and load back with the same geometry.
+    let data = model.to_data();
+    #[cfg_attr(feature = "pure-rust", allow(unused_mut))]
+    let mut saved = vec![data.save(SaveOptions::new()).expect("native encoder")];
+    #[cfg(not(feature = "pure-rust"))]
+    saved.push(model.save(SaveOptions::new()).expect("SDK encoder"));
+    for saved in saved {
+        let reloaded = Model::load(&saved, options())
+            .expect("saved model loads")
+            .model;
+        assert_eq!(reloaded.faces().len(), model.faces().len());
+    }
+
+    // Edited data goes through the same checks as loaded files.
+    let _ = Model::from_data(&data).expect("unchanged data converts back");
+});
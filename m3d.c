@@ -1,2 +0,0 @@
-#define M3D_IMPLEMENTATION
-#include "model3d/m3d.h"
\ No newline at end of file
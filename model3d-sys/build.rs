@@ -0,0 +1,108 @@
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    if env::var_os("CARGO_FEATURE_BINDINGS_ONLY").is_some() {
+        return;
+    }
+
+    #[cfg(feature = "system")]
+    let include = system();
+    #[cfg(not(feature = "system"))]
+    let include = vendored();
+
+    if env::var_os("CARGO_FEATURE_PREBUILT_BINDINGS").is_some() {
+        return;
+    }
+
+    // Only the m3d.h API, the rest of what the header pulls in differs between platforms.
+    let bindings = bindgen::Builder::default()
+        .header(include.join("m3d.h").to_string_lossy())
+        .clang_args(defines().iter().map(|(name, value)| match value {
+            Some(value) => format!("-D{}={}", name, value),
+            None => format!("-D{}", name),
+        }))
+        .allowlist_function("_?m3d_.*")
+        .allowlist_type("_?m3d.*|M3D_.*")
+        .allowlist_var("_?m3d.*|M3D_.*")
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks))
+        .generate()
+        .expect("Unable to generate bindings");
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    bindings
+        .write_to_file(out_path.join("bindings.rs"))
+        .expect("Couldn't write bindings!");
+}
+
+// The switches the header is compiled with, bindgen has to see the same ones.
+fn defines() -> Vec<(&'static str, Option<&'static str>)> {
+    let mut defines = Vec::new();
+    if cfg!(feature = "exporter") {
+        defines.push(("M3D_EXPORTER", None));
+    }
+    if cfg!(feature = "ascii") {
+        defines.push(("M3D_ASCII", None));
+    }
+    if !cfg!(feature = "animation") {
+        defines.push(("M3D_NOANIMATION", None));
+    }
+    if !cfg!(feature = "voxels") {
+        defines.push(("M3D_NOVOXELS", None));
+    }
+    if !cfg!(feature = "importer") {
+        defines.push(("M3D_NOIMPORTER", None));
+    }
+    if cfg!(feature = "double") {
+        defines.push(("M3D_DOUBLE", None));
+    }
+    if cfg!(feature = "small-index") {
+        defines.push(("M3D_SMALLINDEX", None));
+    }
+    defines
+}
+
+#[cfg(not(feature = "system"))]
+fn vendored() -> PathBuf {
+    if !PathBuf::from("vendor/m3d.h").exists() {
+        panic!(
+            "model3d-sys/vendor/m3d.h is missing, it is committed with the crate, \
+             or enable the `system` feature"
+        );
+    }
+    println!("cargo:rerun-if-changed=vendor/m3d.h");
+    println!("cargo:rerun-if-changed=m3d.c");
+
+    // The script hooks and allocator shims are implemented by the `model3d` crate.
+    let mut build = cc::Build::new();
+    for (name, value) in defines() {
+        build.define(name, value);
+    }
+    build
+        .warnings(false)
+        .opt_level(2)
+        .include("vendor")
+        .define("M3D_TX_INTERP", "m3d_rs_txsc")
+        .define("M3D_PR_INTERP", "m3d_rs_prsc")
+        .define("M3D_MALLOC", "m3d_rs_malloc")
+        .define("M3D_REALLOC", "m3d_rs_realloc")
+        .define("M3D_FREE", "m3d_rs_free")
+        .file("m3d.c")
+        .compile("m3d");
+
+    let include = PathBuf::from("vendor");
+    println!(
+        "cargo:include={}",
+        env::current_dir().unwrap().join(&include).display()
+    );
+    include
+}
+
+#[cfg(feature = "system")]
+fn system() -> PathBuf {
+    let lib = pkg_config::probe_library("m3d").expect("libm3d not found by pkg-config");
+    lib.include_paths
+        .into_iter()
+        .find(|path| path.join("m3d.h").exists())
+        .expect("m3d.h not found in the libm3d include paths")
+}
@@ -0,0 +1,9 @@
+#include <stddef.h>
+
+/* Implemented by the `model3d` crate on top of the Rust global allocator. */
+void *m3d_rs_malloc(size_t size);
+void *m3d_rs_realloc(void *ptr, size_t size);
+void m3d_rs_free(void *ptr);
+
+#define M3D_IMPLEMENTATION
+#include "m3d.h"
@@ -0,0 +1,30 @@
+// Checked on every target, unlike the layout tests bindgen generates, which only run with
+// `cargo test`. A mismatch means the prebuilt bindings don't fit the target, run bindgen instead.
+const _: () = {
+    use std::mem::{align_of, size_of};
+    assert!(size_of::<m3dhdr_t>() == 16 && align_of::<m3dhdr_t>() == 1);
+    assert!(size_of::<m3dchunk_t>() == 8 && align_of::<m3dchunk_t>() == 1);
+    assert!(size_of::<m3dti_t>() == 8 && align_of::<m3dti_t>() == 4);
+    assert!(size_of::<m3dtx_t>() == 24 && align_of::<m3dtx_t>() == 8);
+    assert!(size_of::<m3dw_t>() == 8 && align_of::<m3dw_t>() == 4);
+    assert!(size_of::<m3db_t>() == 104 && align_of::<m3db_t>() == 8);
+    assert!(size_of::<m3ds_t>() == 32 && align_of::<m3ds_t>() == 4);
+    assert!(size_of::<m3dv_t>() == 24 && align_of::<m3dv_t>() == 4);
+    assert!(size_of::<m3dpd_t>() == 2 && align_of::<m3dpd_t>() == 1);
+    assert!(size_of::<m3dp_t__bindgen_ty_1>() == 4 && align_of::<m3dp_t__bindgen_ty_1>() == 4);
+    assert!(size_of::<m3dp_t>() == 8 && align_of::<m3dp_t>() == 4);
+    assert!(size_of::<m3dm_t>() == 24 && align_of::<m3dm_t>() == 8);
+    assert!(size_of::<m3df_t>() == 40 && align_of::<m3df_t>() == 4);
+    assert!(size_of::<m3dvi_t>() == 16 && align_of::<m3dvi_t>() == 8);
+    assert!(size_of::<m3dvt_t>() == 40 && align_of::<m3dvt_t>() == 8);
+    assert!(size_of::<m3dvx_t>() == 48 && align_of::<m3dvx_t>() == 8);
+    assert!(size_of::<m3dcd_t>() == 9 && align_of::<m3dcd_t>() == 1);
+    assert!(size_of::<m3dc_t>() == 16 && align_of::<m3dc_t>() == 8);
+    assert!(size_of::<m3dh_t>() == 24 && align_of::<m3dh_t>() == 8);
+    assert!(size_of::<m3dl_t>() == 32 && align_of::<m3dl_t>() == 8);
+    assert!(size_of::<m3dtr_t>() == 12 && align_of::<m3dtr_t>() == 4);
+    assert!(size_of::<m3dfr_t>() == 16 && align_of::<m3dfr_t>() == 8);
+    assert!(size_of::<m3da_t>() == 24 && align_of::<m3da_t>() == 8);
+    assert!(size_of::<m3di_t>() == 24 && align_of::<m3di_t>() == 8);
+    assert!(size_of::<m3d_t>() == 320 && align_of::<m3d_t>() == 8);
+};
@@ -0,0 +1,39 @@
+//! Raw bindings to the Model3D C SDK, see the `model3d` crate for a safe API.
+#![allow(dead_code)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+#[cfg(all(feature = "prebuilt-bindings", not(target_pointer_width = "64")))]
+compile_error!("prebuilt bindings are only available for 64-bit targets");
+
+#[cfg(all(
+    any(feature = "prebuilt-bindings", feature = "bindings-only"),
+    any(feature = "double", feature = "small-index")
+))]
+compile_error!("prebuilt bindings are generated without M3D_DOUBLE and M3D_SMALLINDEX");
+
+#[cfg(all(
+    any(feature = "prebuilt-bindings", feature = "bindings-only"),
+    not(unix),
+    not(all(windows, target_env = "msvc"))
+))]
+compile_error!("prebuilt bindings are only available for windows-msvc and 64-bit unix targets");
+
+// Unedited bindgen output for x86_64-pc-windows-msvc, along with what the MSVC headers define.
+#[cfg(all(
+    any(feature = "prebuilt-bindings", feature = "bindings-only"),
+    windows,
+    target_env = "msvc"
+))]
+include!("bindings/windows_msvc.rs");
+// The same m3d.h items for LP64 unix, where bindgen only emits the allowlisted m3d items and
+// C enums without negative values are unsigned. The types only use fixed width integers,
+// floats and pointers, so they don't differ otherwise.
+#[cfg(all(any(feature = "prebuilt-bindings", feature = "bindings-only"), unix))]
+include!("bindings/unix_lp64.rs");
+#[cfg(any(feature = "prebuilt-bindings", feature = "bindings-only"))]
+include!("bindings/layout.rs");
+
+#[cfg(not(any(feature = "prebuilt-bindings", feature = "bindings-only")))]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
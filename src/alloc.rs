@@ -0,0 +1,91 @@
+use std::alloc::{self, Layout};
+use std::ffi::CStr;
+
+// The C SDK is compiled with M3D_MALLOC, M3D_REALLOC and M3D_FREE pointing at the `m3d_rs_*`
+// shims below, so its allocations go through the Rust global allocator like everything else.
+// Memory handed to or taken from C must use the functions of this module, never `libc`.
+
+// Enough for any C type, `malloc` gives the same guarantee.
+const ALIGN: usize = 16;
+// The size is kept in front of every block, `free` does not get it.
+const HEADER: usize = ALIGN;
+
+fn layout(size: usize) -> Option<Layout> {
+    Layout::from_size_align(size.checked_add(HEADER)?, ALIGN).ok()
+}
+
+unsafe fn start(block: *mut u8, size: usize) -> *mut libc::c_void {
+    if block.is_null() {
+        return std::ptr::null_mut();
+    }
+    (block as *mut usize).write(size);
+    block.add(HEADER) as _
+}
+
+unsafe fn block(ptr: *mut libc::c_void) -> (*mut u8, Layout) {
+    let block = (ptr as *mut u8).sub(HEADER);
+    let size = (block as *const usize).read();
+    (
+        block,
+        Layout::from_size_align_unchecked(size + HEADER, ALIGN),
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn m3d_rs_malloc(size: usize) -> *mut libc::c_void {
+    match layout(size) {
+        Some(layout) => start(alloc::alloc(layout), size),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn m3d_rs_realloc(ptr: *mut libc::c_void, size: usize) -> *mut libc::c_void {
+    if ptr.is_null() {
+        return m3d_rs_malloc(size);
+    }
+    if layout(size).is_none() {
+        return std::ptr::null_mut();
+    }
+
+    let (block, layout) = block(ptr);
+    start(alloc::realloc(block, layout, size + HEADER), size)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn m3d_rs_free(ptr: *mut libc::c_void) {
+    if !ptr.is_null() {
+        let (block, layout) = block(ptr);
+        alloc::dealloc(block, layout);
+    }
+}
+
+// A system libm3d was built against the C allocator, so its memory has to go back there.
+#[cfg(not(feature = "system"))]
+#[cfg_attr(feature = "pure-rust", allow(unused_imports))]
+pub(crate) use self::{m3d_rs_free as free, m3d_rs_malloc as malloc, m3d_rs_realloc as realloc};
+#[cfg(feature = "system")]
+#[cfg_attr(feature = "pure-rust", allow(unused_imports))]
+pub(crate) use libc::{free, malloc, realloc};
+
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+pub(crate) unsafe fn calloc(count: usize, size: usize) -> *mut libc::c_void {
+    let size = match count.checked_mul(size) {
+        Some(size) => size,
+        None => return std::ptr::null_mut(),
+    };
+    let ptr = malloc(size);
+    if !ptr.is_null() {
+        std::ptr::write_bytes(ptr as *mut u8, 0, size);
+    }
+    ptr
+}
+
+pub(crate) unsafe fn strdup(s: &CStr) -> *mut libc::c_char {
+    let bytes = s.to_bytes_with_nul();
+    let ptr = malloc(bytes.len()) as *mut u8;
+    if !ptr.is_null() {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len());
+    }
+    ptr as _
+}
@@ -7,7 +7,7 @@ pub type Weight = m3dc::m3dw_t;
 pub struct Bone(m3dc::m3db_t);
 
 impl Bone {
-    pub fn parent(&self) -> u32 {
+    pub fn parent(&self) -> Index {
         self.0.parent
     }
 
@@ -22,11 +22,11 @@ impl Bone {
         }
     }
 
-    pub fn position(&self) -> u32 {
+    pub fn position(&self) -> Index {
         self.0.pos
     }
 
-    pub fn orientation(&self) -> u32 {
+    pub fn orientation(&self) -> Index {
         self.0.ori
     }
 
@@ -34,7 +34,7 @@ impl Bone {
         unsafe { cptr_to_slice(self.0.weight, self.0.numweight as _) }
     }
 
-    pub fn mat4(&self) -> &[f32; 16usize] {
+    pub fn mat4(&self) -> &[Float; 16usize] {
         &self.0.mat4
     }
 }
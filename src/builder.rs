@@ -0,0 +1,303 @@
+use super::*;
+
+/// Builds a [`ModelData`] from scratch, validating every index as it is added.
+#[derive(Debug, Clone)]
+pub struct ModelBuilder {
+    data: ModelData,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            data: ModelData {
+                scale: 1.0,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+fn check(id: u32, len: usize, err: ErrorKind) -> Result<u32, ErrorKind> {
+    if (id as usize) < len {
+        Ok(id)
+    } else {
+        Err(err)
+    }
+}
+
+impl ModelBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.data.name = name.to_string();
+        self
+    }
+
+    pub fn license(&mut self, license: &str) -> &mut Self {
+        self.data.license = license.to_string();
+        self
+    }
+
+    pub fn author(&mut self, author: &str) -> &mut Self {
+        self.data.author = author.to_string();
+        self
+    }
+
+    pub fn description(&mut self, desc: &str) -> &mut Self {
+        self.data.desc = desc.to_string();
+        self
+    }
+
+    pub fn scale(&mut self, scale: f32) -> &mut Self {
+        self.data.scale = scale;
+        self
+    }
+
+    fn push_vertex(&mut self, [x, y, z]: [f32; 3], w: f32) -> u32 {
+        self.data.vertices.push(Vertex {
+            x,
+            y,
+            z,
+            w,
+            color: 0,
+            skinid: m3dc::M3D_UNDEF,
+        });
+        (self.data.vertices.len() - 1) as _
+    }
+
+    pub fn add_vertex(&mut self, position: [f32; 3]) -> u32 {
+        self.push_vertex(position, 1.0)
+    }
+
+    /// Normals are stored as vertices too, faces reference them by vertex index.
+    pub fn add_normal(&mut self, normal: [f32; 3]) -> u32 {
+        self.push_vertex(normal, 1.0)
+    }
+
+    pub fn set_vertex_color(&mut self, vertex: u32, color: impl Into<Color>) -> Result<(), Error> {
+        let vertex = check(vertex, self.data.vertices.len(), ErrorKind::Vertices)?;
+        self.data.vertices[vertex as usize].color = color.into().into();
+        Ok(())
+    }
+
+    pub fn add_texcoord(&mut self, u: f32, v: f32) -> u32 {
+        self.data.texture_maps.push(TextureMapIndex { u, v });
+        (self.data.texture_maps.len() - 1) as _
+    }
+
+    /// Textures are looked up by name on load. One without pixels has to be resolved then, one with
+    /// pixels is inlined by [`ModelBuilder::save`].
+    pub fn add_texture(&mut self, texture: TextureData) -> Result<u32, Error> {
+        let len = texture.width as usize * texture.height as usize * texture.format as usize;
+        let pixels = texture.pixels.is_empty()
+            || (texture.format != TextureFormat::Invalid && texture.pixels.len() == len);
+        if texture.name.is_empty() || !pixels {
+            return Err(Error::from(ErrorKind::UnknownImg).with_asset(&texture.name));
+        }
+        self.data.textures.push(texture);
+        Ok((self.data.textures.len() - 1) as _)
+    }
+
+    pub fn add_material(&mut self, name: &str) -> Result<u32, Error> {
+        if self.data.materials.iter().any(|m| m.name == name) {
+            return Err(ErrorKind::Material.into());
+        }
+        self.data.materials.push(MaterialData {
+            name: name.to_string(),
+            props: Vec::new(),
+        });
+        Ok((self.data.materials.len() - 1) as _)
+    }
+
+    /// Sets or replaces a property. The value has to match the property's format,
+    /// textures turn the property into its map.
+    pub fn set_material_property(
+        &mut self,
+        material: u32,
+        kind: PropertyKind,
+        value: PropertyValue,
+    ) -> Result<(), Error> {
+        let material = check(material, self.data.materials.len(), ErrorKind::Material)?;
+        let prop = value.to_prop(kind, self.data.textures.len())?;
+
+        let props = &mut self.data.materials[material as usize].props;
+        match props.iter().position(|p| p.type_ == prop.type_) {
+            Some(i) => props[i] = prop,
+            None if props.len() < u8::MAX as usize => props.push(prop),
+            None => return Err(ErrorKind::Truncating.into()),
+        }
+        Ok(())
+    }
+
+    /// Adds a triangle. Texture coordinates and normals are optional, but apply to all three corners.
+    pub fn add_face(
+        &mut self,
+        material: Option<u32>,
+        vertex: [u32; 3],
+        texcoord: Option<[u32; 3]>,
+        normal: Option<[u32; 3]>,
+    ) -> Result<u32, Error> {
+        let d = &self.data;
+        let materialid = match material {
+            Some(id) => check(id, d.materials.len(), ErrorKind::Material)?,
+            None => m3dc::M3D_UNDEF,
+        };
+        let mut face = Face {
+            materialid,
+            vertex,
+            normal: [m3dc::M3D_UNDEF; 3],
+            texcoord: [m3dc::M3D_UNDEF; 3],
+        };
+        for j in 0..3 {
+            check(vertex[j], d.vertices.len(), ErrorKind::Vertices)?;
+            if let Some(texcoord) = texcoord {
+                face.texcoord[j] = check(texcoord[j], d.texture_maps.len(), ErrorKind::TextureMap)?;
+            }
+            if let Some(normal) = normal {
+                face.normal[j] = check(normal[j], d.vertices.len(), ErrorKind::Vertices)?;
+            }
+        }
+
+        self.data.faces.push(face);
+        Ok((self.data.faces.len() - 1) as _)
+    }
+
+    /// Adds a bone relative to its parent, with its orientation as an `[x, y, z, w]` quaternion.
+    pub fn add_bone(
+        &mut self,
+        name: &str,
+        parent: Option<u32>,
+        position: [f32; 3],
+        orientation: [f32; 4],
+    ) -> Result<u32, Error> {
+        let parent = match parent {
+            Some(id) => check(id, self.data.bones.len(), ErrorKind::Bone)?,
+            None => m3dc::M3D_UNDEF,
+        };
+        let position = self.add_vertex(position);
+        let [x, y, z, w] = orientation;
+        let orientation = self.push_vertex([x, y, z], w);
+
+        self.data.bones.push(BoneData {
+            parent,
+            name: name.to_string(),
+            position,
+            orientation,
+            weights: Vec::new(),
+            mat4: [0.0; 16],
+        });
+        Ok((self.data.bones.len() - 1) as _)
+    }
+
+    /// Attaches a vertex to a bone, up to `M3D_NUMBONE` bones per vertex.
+    pub fn add_skin_weight(&mut self, vertex: u32, bone: u32, weight: f32) -> Result<(), Error> {
+        let vertex = check(vertex, self.data.vertices.len(), ErrorKind::Vertices)? as usize;
+        let bone = check(bone, self.data.bones.len(), ErrorKind::Bone)?;
+
+        let d = &mut self.data;
+        if d.vertices[vertex].skinid == m3dc::M3D_UNDEF {
+            d.skins.push(Skin {
+                boneid: [m3dc::M3D_UNDEF; m3dc::M3D_NUMBONE as usize],
+                weight: [0.0; m3dc::M3D_NUMBONE as usize],
+            });
+            d.vertices[vertex].skinid = (d.skins.len() - 1) as _;
+        }
+        let skin = &mut d.skins[d.vertices[vertex].skinid as usize];
+        let slot = skin
+            .boneid
+            .iter()
+            .position(|&id| id == bone || id == m3dc::M3D_UNDEF)
+            .ok_or(ErrorKind::Truncating)?;
+        skin.boneid[slot] = bone;
+        skin.weight[slot] = weight;
+        Ok(())
+    }
+
+    pub fn add_action(&mut self, name: &str, duration_msec: u32) -> u32 {
+        self.data.actions.push(ActionData {
+            name: name.to_string(),
+            duration_msec,
+            frames: Vec::new(),
+        });
+        (self.data.actions.len() - 1) as _
+    }
+
+    pub fn add_frame(&mut self, action: u32, msec: u32) -> Result<u32, Error> {
+        let action = check(action, self.data.actions.len(), ErrorKind::UnknownFrame)?;
+        let frames = &mut self.data.actions[action as usize].frames;
+        frames.push(FrameData {
+            msec,
+            transforms: Vec::new(),
+        });
+        Ok((frames.len() - 1) as _)
+    }
+
+    /// Poses a bone in a frame, like [`ModelBuilder::add_bone`] relative to its parent.
+    pub fn add_transform(
+        &mut self,
+        action: u32,
+        frame: u32,
+        bone: u32,
+        position: [f32; 3],
+        orientation: [f32; 4],
+    ) -> Result<(), Error> {
+        let action = check(action, self.data.actions.len(), ErrorKind::UnknownFrame)? as usize;
+        let frame = check(
+            frame,
+            self.data.actions[action].frames.len(),
+            ErrorKind::UnknownFrame,
+        )? as usize;
+        let boneid = check(bone, self.data.bones.len(), ErrorKind::Bone)?;
+
+        let pos = self.add_vertex(position);
+        let [x, y, z, w] = orientation;
+        let ori = self.push_vertex([x, y, z], w);
+        self.data.actions[action].frames[frame]
+            .transforms
+            .push(Transform { boneid, pos, ori });
+        Ok(())
+    }
+
+    pub fn add_label(
+        &mut self,
+        name: &str,
+        lang: &str,
+        text: &str,
+        vertex: u32,
+        color: impl Into<Color>,
+    ) -> Result<(), Error> {
+        let vertex_id = check(vertex, self.data.vertices.len(), ErrorKind::Vertices)?;
+        self.data.labels.push(LabelData {
+            name: name.to_string(),
+            lang: lang.to_string(),
+            text: text.to_string(),
+            color: color.into().into(),
+            vertex_id,
+        });
+        Ok(())
+    }
+
+    pub fn data(&self) -> &ModelData {
+        &self.data
+    }
+
+    pub fn into_data(self) -> ModelData {
+        self.data
+    }
+
+    /// Bone matrices are left zeroed, they are computed when a saved model is loaded.
+    pub fn build(&self) -> Result<Model, Error> {
+        Model::from_data(&self.data)
+    }
+
+    /// Textures with pixels are always inlined, ASCII models can't hold them and are refused with
+    /// [`ErrorKind::InvalidOptions`].
+    pub fn save(&self, options: SaveOptions) -> Result<Vec<u8>, Error> {
+        if self.data.textures.iter().any(|t| !t.pixels.is_empty()) {
+            return self.data.save(options.inline_textures(true));
+        }
+        self.data.save(options)
+    }
+}
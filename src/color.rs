@@ -0,0 +1,82 @@
+use super::*;
+
+/// An RGBA8 color, packed like Model3D does with red in the low byte.
+///
+/// The color channels are sRGB encoded, alpha is linear.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Color(pub u32);
+
+impl Color {
+    pub const fn from_rgba8(rgba: [u8; 4]) -> Self {
+        Self(u32::from_le_bytes(rgba))
+    }
+
+    pub const fn to_rgba8(self) -> [u8; 4] {
+        self.0.to_le_bytes()
+    }
+
+    /// Channels in `0.0..=1.0`, still sRGB encoded.
+    pub fn to_srgb(self) -> [f32; 4] {
+        self.to_rgba8().map(|c| c as f32 / 255.0)
+    }
+
+    pub fn from_srgb(rgba: [f32; 4]) -> Self {
+        Self::from_rgba8(rgba.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8))
+    }
+
+    /// Channels in `0.0..=1.0` with the sRGB transfer function removed, for lighting and blending.
+    pub fn to_linear(self) -> [f32; 4] {
+        let [r, g, b, a] = self.to_srgb();
+        [decode(r), decode(g), decode(b), a]
+    }
+
+    pub fn from_linear([r, g, b, a]: [f32; 4]) -> Self {
+        Self::from_srgb([encode(r), encode(g), encode(b), a])
+    }
+
+    /// Looks up a color map index, see [`Obj::color_maps`].
+    pub fn from_color_map(color_maps: &[u32], index: Index) -> Option<Self> {
+        color_maps.get(index as usize).copied().map(Color)
+    }
+}
+
+impl From<u32> for Color {
+    fn from(color: u32) -> Self {
+        Color(color)
+    }
+}
+
+impl From<Color> for u32 {
+    fn from(color: Color) -> Self {
+        color.0
+    }
+}
+
+fn decode(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn encode(c: f32) -> f32 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// The [`Color`] of a [`Vertex`], which is a plain C struct.
+pub trait VertexColor {
+    fn color(&self) -> Color;
+}
+
+impl VertexColor for Vertex {
+    fn color(&self) -> Color {
+        Color(self.color)
+    }
+}
@@ -0,0 +1,710 @@
+use super::*;
+use std::ffi::CString;
+use std::io::Write;
+use std::path::Path;
+
+/// Rust owned description of a model, which can be turned into a [`Model`].
+#[derive(Debug, Clone, Default)]
+pub struct ModelData {
+    pub name: String,
+    pub license: String,
+    pub author: String,
+    pub desc: String,
+    pub scale: f32,
+    pub color_maps: Vec<u32>,
+    pub texture_maps: Vec<TextureMapIndex>,
+    pub textures: Vec<TextureData>,
+    pub bones: Vec<BoneData>,
+    pub vertices: Vec<Vertex>,
+    pub skins: Vec<Skin>,
+    pub materials: Vec<MaterialData>,
+    pub faces: Vec<Face>,
+    pub voxel_types: Vec<VoxelTypeData>,
+    pub voxels: Vec<VoxelBlockData>,
+    pub shapes: Vec<ShapeData>,
+    pub labels: Vec<LabelData>,
+    pub actions: Vec<ActionData>,
+    pub inlined: Vec<InlinedData>,
+    pub extra: Vec<Vec<u8>>,
+    pub preview: Vec<u8>,
+    pub(crate) generated_normals: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextureData {
+    pub name: String,
+    pub width: u16,
+    pub height: u16,
+    pub format: TextureFormat,
+    pub pixels: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoneData {
+    pub parent: u32,
+    pub name: String,
+    pub position: u32,
+    pub orientation: u32,
+    pub weights: Vec<Weight>,
+    pub mat4: [f32; 16],
+}
+
+#[derive(Clone, Default)]
+pub struct MaterialData {
+    pub name: String,
+    pub props: Vec<MaterialProp>,
+}
+
+impl std::fmt::Debug for MaterialData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaterialData")
+            .field("name", &self.name)
+            .field(
+                "props",
+                &self
+                    .props
+                    .iter()
+                    .map(|p| (p.type_, unsafe { p.value.num }))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoxelItemData {
+    pub count: u16,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoxelTypeData {
+    pub name: String,
+    pub rotation: u8,
+    pub shape: u16,
+    pub material_id: u32,
+    pub color: u32,
+    pub skin_id: u32,
+    pub items: Vec<VoxelItemData>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoxelBlockData {
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub uncertain: u8,
+    pub group: u8,
+    pub data: Vec<u16>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShapeCommandData {
+    pub kind: u16,
+    pub args: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeData {
+    pub name: String,
+    pub group: u32,
+    pub commands: Vec<ShapeCommandData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelData {
+    pub name: String,
+    pub lang: String,
+    pub text: String,
+    pub color: u32,
+    pub vertex_id: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameData {
+    pub msec: u32,
+    pub transforms: Vec<Transform>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ActionData {
+    pub name: String,
+    pub duration_msec: u32,
+    pub frames: Vec<FrameData>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InlinedData {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl ModelData {
+    /// Parses an ASCII model. Textures are referenced by name only, nothing is resolved.
+    pub fn from_ascii(text: &str) -> Result<ModelData, SyntaxError> {
+        native::ascii::parse(text, ModelData::default()).map(|text| text.model)
+    }
+
+    /// Adds a triangle after checking its material, vertex, normal and texture map indices.
+    pub fn add_face(&mut self, face: Face) -> Result<u32, Error> {
+        let valid = |id: u32, len: usize| id == m3dc::M3D_UNDEF || (id as usize) < len;
+        if !valid(face.materialid, self.materials.len()) {
+            return Err(ErrorKind::Material.into());
+        }
+        for j in 0..3 {
+            if (face.vertex[j] as usize) >= self.vertices.len()
+                || !valid(face.normal[j], self.vertices.len())
+            {
+                return Err(ErrorKind::Vertices.into());
+            }
+            if !valid(face.texcoord[j], self.texture_maps.len()) {
+                return Err(ErrorKind::TextureMap.into());
+            }
+        }
+        self.faces.push(face);
+        Ok((self.faces.len() - 1) as _)
+    }
+
+    pub fn remove_face(&mut self, face: u32) -> Option<Face> {
+        ((face as usize) < self.faces.len()).then(|| self.faces.remove(face as _))
+    }
+
+    pub fn retain_faces(&mut self, f: impl FnMut(&Face) -> bool) {
+        self.faces.retain(f);
+    }
+
+    pub fn rename_bone(&mut self, bone: u32, name: &str) -> Result<(), Error> {
+        let bone = self.bones.get_mut(bone as usize).ok_or(ErrorKind::Bone)?;
+        bone.name = name.to_string();
+        Ok(())
+    }
+
+    pub fn rename_action(&mut self, action: u32, name: &str) -> Result<(), Error> {
+        let action = self
+            .actions
+            .get_mut(action as usize)
+            .ok_or(ErrorKind::UnknownFrame)?;
+        action.name = name.to_string();
+        Ok(())
+    }
+
+    pub fn rename_material(&mut self, material: u32, name: &str) -> Result<(), Error> {
+        if self.materials.iter().any(|m| m.name == name) {
+            return Err(ErrorKind::Material.into());
+        }
+        let material = self
+            .materials
+            .get_mut(material as usize)
+            .ok_or(ErrorKind::Material)?;
+        material.name = name.to_string();
+        Ok(())
+    }
+
+    /// Swaps a texture in place, materials keep referencing it by index. Returns the old one.
+    pub fn replace_texture(
+        &mut self,
+        texture: u32,
+        replacement: TextureData,
+    ) -> Result<TextureData, Error> {
+        let texture = self
+            .textures
+            .get_mut(texture as usize)
+            .ok_or(ErrorKind::UnknownImg)?;
+        Ok(std::mem::replace(texture, replacement))
+    }
+
+    /// Encodes M3D natively, producing the same files as [`Obj::save`].
+    pub fn save(&self, options: SaveOptions) -> Result<Vec<u8>, Error> {
+        let (quality, flags) = options.to_flags()?;
+        native::encode(self, quality, flags).map_err(Error::from)
+    }
+
+    pub fn save_to_writer(
+        &self,
+        mut writer: impl Write,
+        options: SaveOptions,
+    ) -> Result<(), Error> {
+        writer.write_all(&self.save(options)?)?;
+        Ok(())
+    }
+
+    pub fn save_to_file(&self, path: impl AsRef<Path>, options: SaveOptions) -> Result<(), Error> {
+        let path = path.as_ref();
+        let encoded = self.save(options).map_err(|err| err.with_path(path))?;
+        std::fs::write(path, encoded).map_err(|err| Error::from(err).with_path(path))
+    }
+}
+
+impl Model {
+    /// Lays the data out like the C SDK does, so every view type works on the result.
+    /// Indices pointing past what `data` holds are rejected, like they are when loading.
+    pub fn from_data(data: &ModelData) -> Result<Model, Error> {
+        data.validate()?;
+        for texture in &data.textures {
+            // Textures known by name only have no pixels.
+            let len = texture.width as usize * texture.height as usize * texture.format as usize;
+            if !texture.pixels.is_empty() && texture.pixels.len() != len {
+                return Err(ErrorKind::UnknownImg.into());
+            }
+        }
+        let raw = unsafe { export(data) };
+        let raw = std::ptr::NonNull::new(raw).ok_or(ErrorKind::Alloc)?;
+        let model = unsafe { Model::from_exported(raw) };
+        model.validate()?;
+        Ok(model)
+    }
+}
+
+impl From<&Obj> for ModelData {
+    fn from(model: &Obj) -> Self {
+        model.to_data()
+    }
+}
+
+unsafe fn rust_string(s: *const libc::c_char) -> String {
+    if s.is_null() {
+        String::new()
+    } else {
+        cptr_to_str(s as _).to_string()
+    }
+}
+
+unsafe fn array<'a, T>(ptr: *const T, len: u32) -> &'a [T] {
+    cptr_to_slice(ptr, len as _)
+}
+
+// Deep copy of a model, the counterpart of `export`.
+pub(crate) unsafe fn import(m: &m3dc::m3d_t) -> ModelData {
+    let flags = ObjectFlags::from_bits_truncate(m.flags as _);
+
+    ModelData {
+        name: rust_string(m.name),
+        license: rust_string(m.license),
+        author: rust_string(m.author),
+        desc: rust_string(m.desc),
+        scale: m.scale,
+        color_maps: array(m.cmap, m.numcmap).to_vec(),
+        texture_maps: array(m.tmap, m.numtmap).to_vec(),
+        textures: array(m.texture, m.numtexture)
+            .iter()
+            .map(|t| TextureData {
+                name: rust_string(t.name),
+                width: t.w,
+                height: t.h,
+                format: TextureFormat::from(t.f),
+                pixels: array(
+                    t.d,
+                    t.w as u32 * t.h as u32 * TextureFormat::from(t.f) as u32,
+                )
+                .to_vec(),
+            })
+            .collect(),
+        bones: array(m.bone, m.numbone)
+            .iter()
+            .map(|b| BoneData {
+                parent: b.parent,
+                name: rust_string(b.name),
+                position: b.pos,
+                orientation: b.ori,
+                weights: array(b.weight, b.numweight).to_vec(),
+                mat4: b.mat4,
+            })
+            .collect(),
+        vertices: array(m.vertex, m.numvertex).to_vec(),
+        skins: array(m.skin, m.numskin).to_vec(),
+        materials: array(m.material, m.nummaterial)
+            .iter()
+            .map(|mat| MaterialData {
+                name: rust_string(mat.name),
+                props: array(mat.prop, mat.numprop as _).to_vec(),
+            })
+            .collect(),
+        faces: array(m.face, m.numface).to_vec(),
+        voxel_types: array(m.voxtype, m.numvoxtype)
+            .iter()
+            .map(|v| VoxelTypeData {
+                name: rust_string(v.name),
+                rotation: v.rotation,
+                shape: v.voxshape,
+                material_id: v.materialid,
+                color: v.color,
+                skin_id: v.skinid,
+                items: array(v.item, v.numitem as _)
+                    .iter()
+                    .map(|i| VoxelItemData {
+                        count: i.count,
+                        name: rust_string(i.name),
+                    })
+                    .collect(),
+            })
+            .collect(),
+        voxels: array(m.voxel, m.numvoxel)
+            .iter()
+            .map(|v| VoxelBlockData {
+                name: rust_string(v.name),
+                x: v.x,
+                y: v.y,
+                z: v.z,
+                width: v.w,
+                height: v.h,
+                depth: v.d,
+                uncertain: v.uncertain,
+                group: v.groupid,
+                data: array(v.data, v.w * v.h * v.d).to_vec(),
+            })
+            .collect(),
+        shapes: array(m.shape, m.numshape)
+            .iter()
+            .map(|s| ShapeData {
+                name: rust_string(s.name),
+                group: s.group,
+                commands: array(s.cmd, s.numcmd)
+                    .iter()
+                    .map(|c| {
+                        // Validated models hold every argument of the commands they know.
+                        let len = if c.arg.is_null() {
+                            None
+                        } else {
+                            native::command_arg_count(c.type_, |k| *c.arg.add(k))
+                        };
+                        ShapeCommandData {
+                            kind: c.type_,
+                            args: array(c.arg, len.unwrap_or(0) as u32).to_vec(),
+                        }
+                    })
+                    .collect(),
+            })
+            .collect(),
+        labels: array(m.label, m.numlabel)
+            .iter()
+            .map(|l| LabelData {
+                name: rust_string(l.name),
+                lang: rust_string(l.lang),
+                text: rust_string(l.text),
+                color: l.color,
+                vertex_id: l.vertexid,
+            })
+            .collect(),
+        actions: array(m.action, m.numaction)
+            .iter()
+            .map(|a| ActionData {
+                name: rust_string(a.name),
+                duration_msec: a.durationmsec,
+                frames: array(a.frame, a.numframe)
+                    .iter()
+                    .map(|f| FrameData {
+                        msec: f.msec,
+                        transforms: array(f.transform, f.numtransform).to_vec(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+        inlined: array(m.inlined, m.numinlined)
+            .iter()
+            .map(|i| InlinedData {
+                name: rust_string(i.name),
+                data: array(i.data, i.length).to_vec(),
+            })
+            .collect(),
+        extra: array(m.extra, m.numextra)
+            .iter()
+            .filter(|e| !e.is_null())
+            .map(|&e| array(e as *const u8, (*e).length).to_vec())
+            .collect(),
+        preview: array(m.preview.data, m.preview.length).to_vec(),
+        generated_normals: flags.contains(ObjectFlags::GEN_NORM),
+    }
+}
+
+unsafe fn c_array<T: Copy>(items: &[T]) -> *mut T {
+    if items.is_empty() {
+        return std::ptr::null_mut();
+    }
+
+    let ptr = alloc::malloc(std::mem::size_of_val(items)) as *mut T;
+    if !ptr.is_null() {
+        std::ptr::copy_nonoverlapping(items.as_ptr(), ptr, items.len());
+    }
+    ptr
+}
+
+unsafe fn c_array_map<T, U: Copy>(items: &[T], f: impl FnMut(&T) -> U) -> *mut U {
+    c_array(&items.iter().map(f).collect::<Vec<_>>())
+}
+
+unsafe fn c_string(s: &str) -> *mut libc::c_char {
+    // Interior zeroes would truncate the string in C anyway.
+    let s = s.split('\0').next().unwrap_or_default();
+    let s = CString::new(s).unwrap_or_default();
+    alloc::strdup(&s)
+}
+
+// Every array and string is malloc'd, see `free_exported` for the matching release.
+unsafe fn export(data: &ModelData) -> *mut m3dc::m3d_t {
+    let model = alloc::calloc(1, std::mem::size_of::<m3dc::m3d_t>()) as *mut m3dc::m3d_t;
+    if model.is_null() {
+        return model;
+    }
+    let m = &mut *model;
+
+    let mut flags = ObjectFlags::FREE_STR;
+    flags.set(ObjectFlags::GEN_NORM, data.generated_normals);
+    m.flags = flags.bits() as _;
+    m.vc_s = 4;
+    m.vi_s = 4;
+    m.si_s = 4;
+    m.ci_s = 4;
+    m.ti_s = 4;
+    m.bi_s = 4;
+    m.nb_s = m3dc::M3D_NUMBONE as _;
+    m.sk_s = 4;
+    m.fc_s = 4;
+    m.hi_s = 4;
+    m.fi_s = 4;
+    m.vd_s = 4;
+    m.vp_s = 2;
+
+    m.name = c_string(&data.name);
+    m.license = c_string(&data.license);
+    m.author = c_string(&data.author);
+    m.desc = c_string(&data.desc);
+    m.scale = data.scale;
+
+    m.numcmap = data.color_maps.len() as _;
+    m.cmap = c_array(&data.color_maps);
+    m.numtmap = data.texture_maps.len() as _;
+    m.tmap = c_array(&data.texture_maps);
+
+    m.numtexture = data.textures.len() as _;
+    m.texture = c_array_map(&data.textures, |t| m3dc::m3dtx_t {
+        name: c_string(&t.name),
+        d: c_array(&t.pixels),
+        w: t.width,
+        h: t.height,
+        f: t.format as _,
+    });
+
+    m.numbone = data.bones.len() as _;
+    m.bone = c_array_map(&data.bones, |b| m3dc::m3db_t {
+        parent: b.parent,
+        name: c_string(&b.name),
+        pos: b.position,
+        ori: b.orientation,
+        numweight: b.weights.len() as _,
+        weight: c_array(&b.weights),
+        mat4: b.mat4,
+    });
+
+    m.numvertex = data.vertices.len() as _;
+    m.vertex = c_array(&data.vertices);
+    m.numskin = data.skins.len() as _;
+    m.skin = c_array(&data.skins);
+
+    m.nummaterial = data.materials.len() as _;
+    m.material = c_array_map(&data.materials, |mat| {
+        let props = &mat.props[..mat.props.len().min(u8::MAX as usize)];
+        m3dc::m3dm_t {
+            name: c_string(&mat.name),
+            numprop: props.len() as _,
+            prop: c_array(props),
+        }
+    });
+
+    m.numface = data.faces.len() as _;
+    m.face = c_array(&data.faces);
+
+    m.numvoxtype = data.voxel_types.len() as _;
+    m.voxtype = c_array_map(&data.voxel_types, |v| {
+        let items = &v.items[..v.items.len().min(u8::MAX as usize)];
+        m3dc::m3dvt_t {
+            name: c_string(&v.name),
+            rotation: v.rotation,
+            voxshape: v.shape,
+            materialid: v.material_id,
+            color: v.color,
+            skinid: v.skin_id,
+            numitem: items.len() as _,
+            item: c_array_map(items, |i| m3dc::m3dvi_t {
+                count: i.count,
+                name: c_string(&i.name),
+            }),
+        }
+    });
+
+    m.numvoxel = data.voxels.len() as _;
+    m.voxel = c_array_map(&data.voxels, |v| m3dc::m3dvx_t {
+        name: c_string(&v.name),
+        x: v.x,
+        y: v.y,
+        z: v.z,
+        w: v.width,
+        h: v.height,
+        d: v.depth,
+        uncertain: v.uncertain,
+        groupid: v.group,
+        data: c_array(&v.data),
+    });
+
+    m.numshape = data.shapes.len() as _;
+    m.shape = c_array_map(&data.shapes, |s| m3dc::m3dh_t {
+        name: c_string(&s.name),
+        group: s.group,
+        numcmd: s.commands.len() as _,
+        cmd: c_array_map(&s.commands, |c| m3dc::m3dc_t {
+            type_: c.kind,
+            arg: c_array(&c.args),
+        }),
+    });
+
+    m.numlabel = data.labels.len() as _;
+    m.label = c_array_map(&data.labels, |l| m3dc::m3dl_t {
+        name: c_string(&l.name),
+        lang: c_string(&l.lang),
+        text: c_string(&l.text),
+        color: l.color,
+        vertexid: l.vertex_id,
+    });
+
+    m.numaction = data.actions.len() as _;
+    m.action = c_array_map(&data.actions, |a| m3dc::m3da_t {
+        name: c_string(&a.name),
+        durationmsec: a.duration_msec,
+        numframe: a.frames.len() as _,
+        frame: c_array_map(&a.frames, |f| m3dc::m3dfr_t {
+            msec: f.msec,
+            numtransform: f.transforms.len() as _,
+            transform: c_array(&f.transforms),
+        }),
+    });
+
+    m.numinlined = data.inlined.len() as _;
+    m.inlined = c_array_map(&data.inlined, |i| m3dc::m3di_t {
+        name: c_string(&i.name),
+        data: c_array(&i.data),
+        length: i.data.len() as _,
+    });
+
+    m.numextra = data.extra.len() as _;
+    m.extra = c_array_map(&data.extra, |e| c_array(e) as *mut m3dc::m3dchunk_t);
+
+    m.preview = m3dc::m3di_t {
+        name: std::ptr::null_mut(),
+        data: c_array(&data.preview),
+        length: data.preview.len() as _,
+    };
+
+    model
+}
+
+unsafe fn free_array<T>(ptr: *mut T, len: u32, mut f: impl FnMut(&T)) {
+    if ptr.is_null() {
+        return;
+    }
+    for item in cptr_to_slice(ptr, len as _) {
+        f(item);
+    }
+    alloc::free(ptr as _);
+}
+
+pub(crate) unsafe fn free_exported(model: *mut m3dc::m3d_t) {
+    let m = &*model;
+    let free = |ptr: *mut libc::c_char| alloc::free(ptr as _);
+
+    free(m.name);
+    free(m.license);
+    free(m.author);
+    free(m.desc);
+    alloc::free(m.cmap as _);
+    alloc::free(m.tmap as _);
+    free_array(m.texture, m.numtexture, |t| {
+        free(t.name);
+        alloc::free(t.d as _);
+    });
+    free_array(m.bone, m.numbone, |b| {
+        free(b.name);
+        alloc::free(b.weight as _);
+    });
+    alloc::free(m.vertex as _);
+    alloc::free(m.skin as _);
+    free_array(m.material, m.nummaterial, |mat| {
+        free(mat.name);
+        alloc::free(mat.prop as _);
+    });
+    alloc::free(m.face as _);
+    free_array(m.voxtype, m.numvoxtype, |v| {
+        free(v.name);
+        free_array(v.item, v.numitem as _, |i| free(i.name));
+    });
+    free_array(m.voxel, m.numvoxel, |v| {
+        free(v.name);
+        alloc::free(v.data as _);
+    });
+    free_array(m.shape, m.numshape, |s| {
+        free(s.name);
+        free_array(s.cmd, s.numcmd, |c| alloc::free(c.arg as _));
+    });
+    free_array(m.label, m.numlabel, |l| {
+        free(l.name);
+        free(l.lang);
+        free(l.text);
+    });
+    free_array(m.action, m.numaction, |a| {
+        free(a.name);
+        free_array(a.frame, a.numframe, |f| alloc::free(f.transform as _));
+    });
+    free_array(m.inlined, m.numinlined, |i| {
+        free(i.name);
+        alloc::free(i.data as _);
+    });
+    free_array(m.extra, m.numextra, |e| alloc::free(*e as _));
+    alloc::free(m.preview.data as _);
+    alloc::free(model as _);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A polygon through three vertices, exported and then broken through the C structs.
+    fn exported_polygon(args: Vec<u32>, corrupt: impl FnOnce(&mut m3dc::m3dc_t)) -> Model {
+        let mut data = ModelData::default();
+        data.shapes.push(ShapeData {
+            name: "outline".to_string(),
+            group: m3dc::M3D_UNDEF,
+            commands: vec![ShapeCommandData { kind: 31, args }],
+        });
+        unsafe {
+            let raw = export(&data);
+            corrupt(&mut *(*(*raw).shape).cmd);
+            Model::from_exported(std::ptr::NonNull::new(raw).unwrap())
+        }
+    }
+
+    #[test]
+    fn commands_without_their_arguments_are_refused() {
+        let model = exported_polygon(vec![3, 0, 1, 2], |_| ());
+        assert!(model.validate().is_ok());
+        assert_eq!(model.to_data().shapes[0].commands[0].args, [3, 0, 1, 2]);
+
+        let model = exported_polygon(vec![3, 0, 1, 2], |c| unsafe { *c.arg = 0 });
+        let err = model.validate().unwrap_err();
+        assert_eq!((err.kind(), err.chunk()), (ErrorKind::Shape, Some("SHPE")));
+
+        let model = exported_polygon(vec![3, 0, 1, 2], |c| {
+            unsafe { alloc::free(c.arg as _) };
+            c.arg = std::ptr::null_mut();
+        });
+        assert_eq!(model.validate().unwrap_err().kind(), ErrorKind::Shape);
+        // Nothing is read through the missing arguments.
+        assert!(model.to_data().shapes[0].commands[0].args.is_empty());
+
+        // Unknown commands are skipped, like the SDK does.
+        let model = exported_polygon(vec![3, 0, 1, 2], |c| c.type_ = 1000);
+        assert!(model.validate().is_ok());
+        assert!(model.to_data().shapes[0].commands[0].args.is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+use super::*;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// What went wrong, the C SDK error codes plus the wrapper's own.
+#[repr(i8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, num_enum::FromPrimitive)]
+pub enum ErrorKind {
+    Alloc = m3dc::M3D_ERR_ALLOC as _,
+    BadFile = m3dc::M3D_ERR_BADFILE as _,
+    Unimplemented = m3dc::M3D_ERR_UNIMPL as _,
+    UnkownProperty = m3dc::M3D_ERR_UNKPROP as _,
+    UnknownMesh = m3dc::M3D_ERR_UNKMESH as _,
+    UnknownImg = m3dc::M3D_ERR_UNKIMG as _,
+    UnknownFrame = m3dc::M3D_ERR_UNKFRAME as _,
+    UnknownCmd = m3dc::M3D_ERR_UNKCMD as _,
+    UnknownkVoxex = m3dc::M3D_ERR_UNKVOX as _,
+    Truncating = m3dc::M3D_ERR_TRUNC as _,
+    ColorMap = m3dc::M3D_ERR_CMAP as _,
+    TextureMap = m3dc::M3D_ERR_TMAP as _,
+    Vertices = m3dc::M3D_ERR_VRTS as _,
+    Bone = m3dc::M3D_ERR_BONE as _,
+    Material = m3dc::M3D_ERR_MTRL as _,
+    Shape = m3dc::M3D_ERR_SHPE as _,
+    VoxelType = m3dc::M3D_ERR_VOXT as _,
+
+    /** Additional Errors for the Rust wrapper */
+    ReturnedNull = i8::MIN,
+    Syntax = i8::MIN + 1,
+    LimitExceeded = i8::MIN + 2,
+    InvalidOptions = i8::MIN + 3,
+    Io = i8::MAX - 1,
+    FileNotFound = i8::MAX,
+
+    #[num_enum(default)]
+    UnknownError = 0,
+}
+
+impl ErrorKind {
+    /// Fatal errors leave no model behind, the others are reported as [`Warning`]s by the loader.
+    pub fn is_fatal(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Alloc
+                | ErrorKind::BadFile
+                | ErrorKind::ReturnedNull
+                | ErrorKind::Syntax
+                | ErrorKind::LimitExceeded
+                | ErrorKind::InvalidOptions
+                | ErrorKind::Io
+                | ErrorKind::FileNotFound
+                | ErrorKind::UnknownError
+        )
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            ErrorKind::Alloc => "out of memory",
+            ErrorKind::BadFile => "not a valid model file",
+            ErrorKind::Unimplemented => "not supported",
+            ErrorKind::UnkownProperty => "unknown material property",
+            ErrorKind::UnknownMesh => "invalid mesh",
+            ErrorKind::UnknownImg => "texture could not be loaded",
+            ErrorKind::UnknownFrame => "invalid animation frame",
+            ErrorKind::UnknownCmd => "unknown shape command",
+            ErrorKind::UnknownkVoxex => "invalid voxel data",
+            ErrorKind::Truncating => "value out of range, truncated",
+            ErrorKind::ColorMap => "invalid color map",
+            ErrorKind::TextureMap => "invalid texture map",
+            ErrorKind::Vertices => "invalid vertex",
+            ErrorKind::Bone => "invalid bone",
+            ErrorKind::Material => "invalid material",
+            ErrorKind::Shape => "invalid shape",
+            ErrorKind::VoxelType => "invalid voxel type",
+            ErrorKind::ReturnedNull => "the SDK returned no model",
+            ErrorKind::Syntax => "syntax error",
+            ErrorKind::LimitExceeded => "resource limit exceeded",
+            ErrorKind::InvalidOptions => "conflicting options",
+            ErrorKind::Io => "i/o error",
+            ErrorKind::FileNotFound => "file not found",
+            ErrorKind::UnknownError => "unknown error",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+/// An [`ErrorKind`] along with what was being read when it happened, where that is known.
+#[derive(Debug, Clone)]
+pub struct Error {
+    kind: ErrorKind,
+    // Boxed, so results stay small on the happy path.
+    context: Option<Box<Context>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Context {
+    io: Option<Arc<io::Error>>,
+    path: Option<PathBuf>,
+    asset: Option<String>,
+    chunk: Option<String>,
+    syntax: Option<SyntaxError>,
+    limit: Option<&'static str>,
+}
+
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn is_fatal(&self) -> bool {
+        self.kind.is_fatal()
+    }
+
+    /// The file being read.
+    pub fn path(&self) -> Option<&Path> {
+        self.context.as_ref()?.path.as_deref()
+    }
+
+    /// The external or inlined asset being resolved, like a texture or a procedural script.
+    pub fn asset(&self) -> Option<&str> {
+        self.context.as_ref()?.asset.as_deref()
+    }
+
+    /// The binary chunk magic, or the ASCII section, that failed to decode.
+    pub fn chunk(&self) -> Option<&str> {
+        self.context.as_ref()?.chunk.as_deref()
+    }
+
+    pub fn syntax(&self) -> Option<&SyntaxError> {
+        self.context.as_ref()?.syntax.as_ref()
+    }
+
+    /// The [`Limits`] field that was exceeded.
+    pub fn limit(&self) -> Option<&str> {
+        self.context.as_ref()?.limit
+    }
+
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.context.as_ref()?.io.as_deref()
+    }
+
+    fn context(mut self, f: impl FnOnce(&mut Context)) -> Self {
+        f(self.context.get_or_insert_with(Default::default));
+        self
+    }
+
+    pub(crate) fn with_limit(self, limit: &'static str) -> Self {
+        self.context(|c| c.limit = Some(limit))
+    }
+
+    pub(crate) fn with_path(self, path: &Path) -> Self {
+        self.context(|c| c.path = Some(path.to_path_buf()))
+    }
+
+    #[cfg_attr(
+        all(not(feature = "pure-rust"), not(feature = "importer")),
+        allow(dead_code)
+    )]
+    pub(crate) fn with_asset(self, asset: &str) -> Self {
+        self.context(|c| c.asset = Some(asset.to_string()))
+    }
+
+    pub(crate) fn with_chunk(self, chunk: &str) -> Self {
+        self.context(|c| c.chunk = Some(chunk.to_string()))
+    }
+
+    pub(crate) fn with_syntax(self, syntax: SyntaxError) -> Self {
+        self.context(|c| c.syntax = Some(syntax))
+    }
+}
+
+impl PartialEq<ErrorKind> for Error {
+    fn eq(&self, kind: &ErrorKind) -> bool {
+        self.kind == *kind
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            context: None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        let kind = match err.kind() {
+            io::ErrorKind::NotFound => ErrorKind::FileNotFound,
+            _ => ErrorKind::Io,
+        };
+        Error::from(kind).context(|c| c.io = Some(Arc::new(err)))
+    }
+}
+
+impl From<SyntaxError> for Error {
+    fn from(err: SyntaxError) -> Self {
+        Error::from(ErrorKind::Syntax).with_syntax(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(syntax) = self.syntax() {
+            write!(f, " at {}", syntax)?;
+        }
+        if let Some(limit) = self.limit() {
+            write!(f, " for {}", limit)?;
+        }
+        if let Some(chunk) = self.chunk() {
+            write!(f, " in {}", chunk)?;
+        }
+        if let Some(asset) = self.asset() {
+            write!(f, " while resolving `{}`", asset)?;
+        }
+        if let Some(path) = self.path() {
+            write!(f, " ({})", path.display())?;
+        }
+        if let Some(io) = self.io_error() {
+            write!(f, ": {}", io)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.io_error()
+            .map(|io| io as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// A problem the loader recovered from, the model is still usable.
+#[derive(Debug, Clone)]
+pub struct Warning(pub Error);
+
+/// Where and why an ASCII model failed to parse. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for SyntaxError {}
@@ -0,0 +1,28 @@
+use super::*;
+
+/// A text annotation attached to a vertex.
+#[repr(transparent)]
+#[derive(Debug, Clone)]
+pub struct Label(m3dc::m3dl_t);
+
+impl Label {
+    pub fn name(&self) -> &str {
+        unsafe { cptr_to_str(self.0.name) }
+    }
+
+    pub fn lang(&self) -> &str {
+        unsafe { cptr_to_str(self.0.lang) }
+    }
+
+    pub fn text(&self) -> &str {
+        unsafe { cptr_to_str(self.0.text) }
+    }
+
+    pub fn color(&self) -> Color {
+        Color(self.0.color)
+    }
+
+    pub fn vertex(&self) -> Index {
+        self.0.vertexid
+    }
+}
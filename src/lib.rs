@@ -1,9 +1,13 @@
-#[allow(dead_code)]
-#[allow(non_upper_case_globals)]
-#[allow(non_camel_case_types)]
-#[allow(non_snake_case)]
-mod bindings;
-use bindings as m3dc;
+use model3d_sys as m3dc;
+
+#[cfg(all(
+    feature = "pure-rust",
+    any(feature = "double", feature = "small-index")
+))]
+compile_error!("the pure-rust decoder only supports single precision floats and 32-bit indices");
+
+pub type Float = m3dc::M3D_FLOAT;
+pub type Index = m3dc::M3D_INDEX;
 
 pub type Vertex = m3dc::m3dv_t;
 pub type Skin = m3dc::m3ds_t;
@@ -12,23 +16,54 @@ pub type Transform = m3dc::m3dtr_t;
 pub type TextureMapIndex = m3dc::m3dti_t;
 
 pub mod action;
+mod alloc;
 pub mod bone;
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+pub mod builder;
+pub mod color;
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+pub mod data;
+pub mod error;
 pub mod inlined_texture;
+pub mod label;
+pub mod loader;
 pub mod material;
+pub mod model;
+mod native;
+pub mod pbr;
+pub mod resolver;
+pub mod save;
+pub mod script;
 pub mod texture;
+mod validate;
+pub mod voxel;
 
 pub use action::*;
 pub use bitflags::bitflags;
 pub use bone::*;
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+pub use builder::*;
+pub use color::*;
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+pub use data::*;
+pub use error::*;
 pub use inlined_texture::*;
-use libc::c_void;
+pub use label::*;
+pub use loader::*;
 pub use material::*;
+pub use model::*;
 pub use object::*;
+pub use pbr::*;
+pub use resolver::*;
+pub use save::*;
+pub use script::*;
 pub use texture::*;
+pub use voxel::*;
 
 pub mod object;
 
-use self::bindings::m3d_save;
+#[cfg(all(not(feature = "pure-rust"), feature = "exporter"))]
+use m3dc::m3d_save;
 use std::ffi::CStr;
 
 bitflags! {
@@ -70,80 +105,24 @@ pub enum QuantizeQuality {
     F64 = m3dc::M3D_EXP_DOUBLE as _,
 }
 
-#[repr(i8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, num_enum::FromPrimitive)]
-pub enum Error {
-    Alloc = m3dc::M3D_ERR_ALLOC as _,
-    BadFile = m3dc::M3D_ERR_BADFILE as _,
-    Unimplemented = m3dc::M3D_ERR_UNIMPL as _,
-    UnkownProperty = m3dc::M3D_ERR_UNKPROP as _,
-    UnknownMesh = m3dc::M3D_ERR_UNKMESH as _,
-    UnknownImg = m3dc::M3D_ERR_UNKIMG as _,
-    UnknownFrame = m3dc::M3D_ERR_UNKFRAME as _,
-    UnknownCmd = m3dc::M3D_ERR_UNKCMD as _,
-    UnknownkVoxex = m3dc::M3D_ERR_UNKVOX as _,
-    Truncating = m3dc::M3D_ERR_TRUNC as _,
-    ColorMap = m3dc::M3D_ERR_CMAP as _,
-    TextureMap = m3dc::M3D_ERR_TMAP as _,
-    Vertices = m3dc::M3D_ERR_VRTS as _,
-    Bone = m3dc::M3D_ERR_BONE as _,
-    Material = m3dc::M3D_ERR_MTRL as _,
-    Shape = m3dc::M3D_ERR_SHPE as _,
-    VoxelType = m3dc::M3D_ERR_VOXT as _,
-
-    /** Additional Errors for the Rust wrapper */
-    ReturnedNull = i8::MIN,
-    FileNotFound = i8::MAX,
-
-    #[num_enum(default)]
-    UnknownError = 0,
-}
-
-unsafe fn cptr_to_str<'a>(cstr_ptr: *const i8) -> &'a str {
-    assert!(!cstr_ptr.is_null());
-    let result = CStr::from_ptr(cstr_ptr).to_str().ok();
-    if let Some(str) = result {
-        str
-    } else {
-        ""
+unsafe fn cptr_to_str<'a>(cstr_ptr: *const libc::c_char) -> &'a str {
+    // Names are optional in the format, a missing one reads as empty.
+    if cstr_ptr.is_null() {
+        return "";
     }
+    CStr::from_ptr(cstr_ptr).to_str().unwrap_or_default()
 }
 
 unsafe fn cptr_to_slice<'a, T>(cptr: *const T, len: usize) -> &'a [T] {
-    assert!(len < std::isize::MAX as _);
-    if !cptr.is_null() || len == 0 {
+    assert!(len < isize::MAX as _);
+    if !cptr.is_null() && len > 0 {
         std::slice::from_raw_parts(cptr, len as _)
     } else {
         &[]
     }
 }
 
-unsafe extern "C" fn m3dread_default(
-    filename: *mut libc::c_char,
-    size: *mut libc::c_uint,
-) -> *mut libc::c_uchar {
-    let mut ret: *mut u8 = std::ptr::null_mut();
-
-    let mode = std::ffi::CString::new("rb").unwrap();
-    let file = libc::fopen(filename, mode.as_ptr());
-
-    if !file.is_null() {
-        libc::fseek(file, 0, libc::SEEK_END);
-        *size = libc::ftell(file) as _;
-        libc::fseek(file, 0, libc::SEEK_SET);
-        ret = libc::malloc((*size + 1) as _) as _;
-        if !ret.is_null() {
-            libc::fread(ret as _, *size as _, 1, file);
-            *(ret.offset(*size as _)) = 0;
-        } else {
-            *size = 0;
-        }
-        libc::fclose(file);
-    }
-
-    ret
-}
-
-unsafe extern "C" fn m3dfree_default(to_free: *mut c_void) {
-    libc::free(to_free as _)
+#[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+unsafe extern "C" fn m3dfree_default(to_free: *mut libc::c_void) {
+    alloc::free(to_free)
 }
@@ -0,0 +1,339 @@
+use super::*;
+use std::cell::Cell;
+#[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+use std::cell::RefCell;
+#[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+use std::ffi::CString;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+mod limits;
+#[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+mod sdk;
+
+pub use limits::Limits;
+
+/// Configures how a model and its external assets are loaded, see [`Model::load`].
+///
+/// There is no option to skip animations or textures. The SDK decodes both along with the rest of
+/// the model, and asks for textures through the same callback as for procedural scripts. [`Limits`]
+/// bound what they cost, and a resolver without the images leaves textures out with a warning.
+pub struct LoadOptions<'a> {
+    resolver: Option<&'a dyn AssetResolver>,
+    texture_script: Option<&'a dyn TextureScriptHandler>,
+    procedural: Option<&'a dyn ProceduralModelHandler>,
+    mtllib: Option<Model>,
+    strict: bool,
+    generate_normals: bool,
+    ignore_inlined_assets: bool,
+    limits: Limits,
+}
+
+impl Default for LoadOptions<'_> {
+    fn default() -> Self {
+        Self {
+            resolver: None,
+            texture_script: None,
+            procedural: None,
+            mtllib: None,
+            strict: false,
+            generate_normals: true,
+            ignore_inlined_assets: false,
+            limits: Limits::unlimited(),
+        }
+    }
+}
+
+/// A loaded model along with the problems the loader recovered from.
+pub struct LoadReport {
+    pub model: Model,
+    pub warnings: Vec<Warning>,
+}
+
+/// Where [`Model::load`] reads a model from.
+pub enum Source<'s> {
+    Bytes(&'s [u8]),
+    /// Assets are resolved next to the file, then in the working directory.
+    Path(&'s Path),
+    Reader(&'s mut dyn Read),
+}
+
+impl<'s> From<&'s [u8]> for Source<'s> {
+    fn from(data: &'s [u8]) -> Self {
+        Source::Bytes(data)
+    }
+}
+
+impl<'s, const N: usize> From<&'s [u8; N]> for Source<'s> {
+    fn from(data: &'s [u8; N]) -> Self {
+        Source::Bytes(data)
+    }
+}
+
+impl<'s> From<&'s Vec<u8>> for Source<'s> {
+    fn from(data: &'s Vec<u8>) -> Self {
+        Source::Bytes(data)
+    }
+}
+
+impl<'s> From<&'s Path> for Source<'s> {
+    fn from(path: &'s Path) -> Self {
+        Source::Path(path)
+    }
+}
+
+impl<'s> From<&'s PathBuf> for Source<'s> {
+    fn from(path: &'s PathBuf) -> Self {
+        Source::Path(path)
+    }
+}
+
+impl<'s, R: Read> From<&'s mut R> for Source<'s> {
+    fn from(reader: &'s mut R) -> Self {
+        Source::Reader(reader)
+    }
+}
+
+impl<'a> LoadOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn resolver(mut self, resolver: &'a dyn AssetResolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    pub fn texture_script(mut self, handler: &'a dyn TextureScriptHandler) -> Self {
+        self.texture_script = Some(handler);
+        self
+    }
+
+    pub fn procedural(mut self, handler: &'a dyn ProceduralModelHandler) -> Self {
+        self.procedural = Some(handler);
+        self
+    }
+
+    pub fn mtllib(mut self, mtllib: Model) -> Self {
+        self.mtllib = Some(mtllib);
+        self
+    }
+
+    /// Fails on warnings too, instead of only on fatal errors.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Faces without normals get one averaged normal per vertex. On by default, like the C SDK.
+    pub fn generate_normals(mut self, generate: bool) -> Self {
+        self.generate_normals = generate;
+        self
+    }
+
+    /// Drops inlined assets from the model, textures already decoded from them are kept.
+    pub fn ignore_inlined_assets(mut self, ignore: bool) -> Self {
+        self.ignore_inlined_assets = ignore;
+        self
+    }
+
+    /// Rejects models exceeding `limits` with [`ErrorKind::LimitExceeded`].
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub(crate) fn load_source(self, source: Source) -> Result<LoadReport, Error> {
+        match source {
+            // Without a resolver, assets are looked up in the working directory.
+            Source::Bytes(data) => self.load(data, &DirResolver::new("")),
+            Source::Reader(reader) => {
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+                self.load(&data, &DirResolver::new(""))
+            }
+            Source::Path(path) => {
+                let data = std::fs::read(path).map_err(|err| Error::from(err).with_path(path))?;
+                let parent = path.parent().unwrap_or_else(|| Path::new(""));
+                let fallback = ChainResolver::new()
+                    .with(DirResolver::new(parent))
+                    .with(DirResolver::new(""));
+                self.load(&data, &fallback)
+                    .map_err(|err| err.with_path(path))
+            }
+        }
+    }
+
+    fn load(self, data: &[u8], fallback: &dyn AssetResolver) -> Result<LoadReport, Error> {
+        let limits = self.limits;
+        let mtllib_depth = self.mtllib.as_ref().map_or(0, |m| m.mtllib_depth + 1);
+        limits.check(
+            "material library depth",
+            mtllib_depth,
+            limits.max_mtllib_depth,
+        )?;
+        let inflated = limits.check_file(data)?;
+        let data = inflated.as_deref().unwrap_or(data);
+
+        let context = LoadContext {
+            resolver: self.resolver.unwrap_or(fallback),
+            texture_script: self.texture_script,
+            procedural: self.procedural,
+            generate_normals: self.generate_normals,
+            limits,
+            exceeded: Cell::new(None),
+            #[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+            strings: RefCell::new(Vec::new()),
+        };
+
+        // The SDK only keeps the last error it ran into.
+        #[cfg(not(feature = "pure-rust"))]
+        let (model, warnings) = {
+            #[cfg(not(any(feature = "double", feature = "small-index")))]
+            let seed = mtllib_seed(self.mtllib.as_deref());
+            let result = context.load_sdk(data, self.mtllib, self.ignore_inlined_assets);
+            // The native parser is stricter than the SDK, it only tells where the SDK gave up.
+            #[cfg(not(any(feature = "double", feature = "small-index")))]
+            if result.as_ref().map_or(true, |model| {
+                ErrorKind::from(model.errcode()) == ErrorKind::BadFile
+            }) {
+                check_syntax(data, seed)?;
+            }
+            let model = result?;
+            let errcode = model.errcode();
+            let warnings = if errcode == m3dc::M3D_SUCCESS as i8 {
+                Vec::new()
+            } else {
+                vec![Error::from(ErrorKind::from(errcode))]
+            };
+            (model, warnings)
+        };
+
+        #[cfg(feature = "pure-rust")]
+        let (model, warnings) = {
+            let (mut data, warnings) = native::decode(data, &context, self.mtllib.as_deref())?;
+            if self.ignore_inlined_assets {
+                data.inlined.clear();
+            }
+            let model = Model::from_data(&data)?;
+            model.set_errcode(warnings.last().map(Error::kind));
+            (model, warnings)
+        };
+
+        let mut model = model;
+        if let Some(err) = context.exceeded.take() {
+            return Err(err);
+        }
+        limits.check_model(&model)?;
+        // `Model::from_data` validates what the native decoder produced.
+        #[cfg(not(feature = "pure-rust"))]
+        model.validate()?;
+        model.mtllib_depth = mtllib_depth;
+
+        if let Some(err) = warnings.iter().find(|err| self.strict || err.is_fatal()) {
+            return Err(err.clone());
+        }
+        Ok(LoadReport {
+            model,
+            warnings: warnings.into_iter().map(Warning).collect(),
+        })
+    }
+}
+
+// The SDK tells that a text model failed to parse but not where, the native parser finds that.
+#[cfg(all(
+    not(feature = "pure-rust"),
+    not(any(feature = "double", feature = "small-index"))
+))]
+fn check_syntax(data: &[u8], seed: ModelData) -> Result<(), Error> {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) if text.starts_with("3dmodel") => text,
+        _ => return Ok(()),
+    };
+    native::ascii::parse(text, seed)?;
+    Ok(())
+}
+
+// Materials of the library can be used by name.
+#[cfg(all(
+    not(feature = "pure-rust"),
+    not(any(feature = "double", feature = "small-index"))
+))]
+fn mtllib_seed(mtllib: Option<&Obj>) -> ModelData {
+    let mut seed = ModelData::default();
+    if let Some(mtllib) = mtllib {
+        seed.materials = mtllib
+            .materials()
+            .iter()
+            .map(|m| MaterialData {
+                name: m.name().to_string(),
+                props: Vec::new(),
+            })
+            .collect();
+    }
+    seed
+}
+
+// State shared with the asset and script hooks for the duration of a load.
+#[cfg_attr(
+    all(not(feature = "pure-rust"), not(feature = "importer")),
+    allow(dead_code)
+)]
+pub(crate) struct LoadContext<'a> {
+    pub resolver: &'a dyn AssetResolver,
+    pub texture_script: Option<&'a dyn TextureScriptHandler>,
+    pub procedural: Option<&'a dyn ProceduralModelHandler>,
+    pub generate_normals: bool,
+    pub limits: Limits,
+    // The first limit an asset ran into, the load fails with it once the decoder returns.
+    pub exceeded: Cell<Option<Error>>,
+    // Strings added by procedurals to models which don't own their strings.
+    #[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+    pub strings: RefCell<Vec<CString>>,
+}
+
+#[cfg_attr(
+    all(not(feature = "pure-rust"), not(feature = "importer")),
+    allow(dead_code)
+)]
+impl LoadContext<'_> {
+    // Assets over the limits are withheld from the decoder.
+    pub fn resolve(&self, name: &str) -> Option<Vec<u8>> {
+        let data = self.resolver.resolve(name)?;
+        match self.limits.check_png(&data) {
+            Ok(()) => Some(data),
+            Err(err) => {
+                self.exceed(err.with_asset(name));
+                None
+            }
+        }
+    }
+
+    pub fn allow_texture(&self, name: &str, width: u16, height: u16) -> bool {
+        match self.limits.check_texture(width as _, height as _) {
+            Ok(()) => true,
+            Err(err) => {
+                self.exceed(err.with_asset(name));
+                false
+            }
+        }
+    }
+
+    fn exceed(&self, err: Error) {
+        let first = self.exceeded.take().unwrap_or(err);
+        self.exceeded.set(Some(first));
+    }
+}
+
+#[cfg(all(not(feature = "pure-rust"), not(feature = "importer")))]
+impl LoadContext<'_> {
+    // Built without the C importer, so there is nothing to load with.
+    fn load_sdk(
+        &self,
+        _data: &[u8],
+        _mtllib: Option<Model>,
+        _ignore_inlined: bool,
+    ) -> Result<Model, Error> {
+        Err(ErrorKind::Unimplemented.into())
+    }
+}
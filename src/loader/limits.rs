@@ -0,0 +1,268 @@
+use super::*;
+use crate::native::Sizes;
+
+/// Caps on what a model may claim, for loading untrusted files.
+///
+/// Binary headers and chunk sizes are checked before anything is decoded, textures as they are
+/// resolved, and the counts again on the decoded model. [`Limits::default`] fits typical game
+/// assets, [`LoadOptions`] start out [`Limits::unlimited`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Size of the model once inflated, and of ASCII models as is.
+    pub max_decompressed_size: usize,
+    pub max_vertices: usize,
+    pub max_faces: usize,
+    pub max_bones: usize,
+    pub max_skins: usize,
+    /// Frames of all actions together.
+    pub max_frames: usize,
+    /// Bone transforms of all frames together.
+    pub max_transforms: usize,
+    /// Voxels of all blocks together, empty ones included.
+    pub max_voxels: usize,
+    /// Width and height of every texture.
+    pub max_texture_size: u32,
+    /// How many material libraries may be chained, a model loaded against a library loaded against
+    /// another one is 2 deep.
+    pub max_mtllib_depth: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_decompressed_size: 256 << 20,
+            max_vertices: 1 << 24,
+            max_faces: 1 << 24,
+            max_bones: 1 << 16,
+            max_skins: 1 << 24,
+            max_frames: 1 << 20,
+            max_transforms: 1 << 24,
+            max_voxels: 1 << 26,
+            max_texture_size: 8192,
+            max_mtllib_depth: 4,
+        }
+    }
+}
+
+impl Limits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_decompressed_size: usize::MAX,
+            max_vertices: usize::MAX,
+            max_faces: usize::MAX,
+            max_bones: usize::MAX,
+            max_skins: usize::MAX,
+            max_frames: usize::MAX,
+            max_transforms: usize::MAX,
+            max_voxels: usize::MAX,
+            max_texture_size: u32::MAX,
+            max_mtllib_depth: usize::MAX,
+        }
+    }
+
+    pub(crate) fn check(&self, limit: &'static str, value: usize, max: usize) -> Result<(), Error> {
+        if value > max {
+            return Err(Error::from(ErrorKind::LimitExceeded).with_limit(limit));
+        }
+        Ok(())
+    }
+
+    /// PNG dimensions are read from the header, so the image is never decoded.
+    pub(crate) fn check_png(&self, data: &[u8]) -> Result<(), Error> {
+        if data.len() >= 24 && data.starts_with(b"\x89PNG") && &data[12..16] == b"IHDR" {
+            let width = u32::from_be_bytes(data[16..20].try_into().unwrap());
+            let height = u32::from_be_bytes(data[20..24].try_into().unwrap());
+            self.check_texture(width, height)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_texture(&self, width: u32, height: u32) -> Result<(), Error> {
+        let max = self.max_texture_size as usize;
+        self.check("texture size", width.max(height) as usize, max)
+    }
+
+    pub(crate) fn check_model(&self, model: &Obj) -> Result<(), Error> {
+        // Generated normals double the vertices, they don't count against the file.
+        let mut vertices = model.vertices().len();
+        if model.flags().contains(ObjectFlags::GEN_NORM) {
+            vertices /= 2;
+        }
+        self.check("vertices", vertices, self.max_vertices)?;
+        self.check("faces", model.faces().len(), self.max_faces)?;
+        self.check("bones", model.bones().len(), self.max_bones)?;
+        self.check("skins", model.skins().len(), self.max_skins)?;
+        let frames = model.actions().iter().flat_map(|a| a.frames());
+        self.check("frames", frames.clone().count(), self.max_frames)?;
+        let transforms = frames.map(|f| f.transforms().len()).sum();
+        self.check("transforms", transforms, self.max_transforms)?;
+        let voxels = model.voxels().iter().fold(0usize, |sum, block| {
+            let [w, h, d] = block.size().map(|c| c as usize);
+            sum.saturating_add(w.saturating_mul(h).saturating_mul(d))
+        });
+        self.check("voxels", voxels, self.max_voxels)?;
+        for texture in model.textures() {
+            self.check_texture(texture.width() as _, texture.height() as _)?;
+        }
+        Ok(())
+    }
+
+    /// Checks what a binary model claims before the decoder allocates for it.
+    ///
+    /// A compressed model is inflated to be checked, and handed back uncompressed so that the
+    /// decoder doesn't inflate it again.
+    pub(crate) fn check_file(&self, data: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        if !data.starts_with(b"3DMO") || data.len() < 8 {
+            // ASCII models allocate in proportion to their text.
+            self.check("decompressed size", data.len(), self.max_decompressed_size)?;
+            return Ok(None);
+        }
+
+        let len = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+        let mut body = &data[8..len.clamp(8, data.len())];
+        let mut preview: &[u8] = &[];
+        if body.starts_with(b"PRVW") && body.len() >= 8 {
+            let len = u32::from_le_bytes(body[4..8].try_into().unwrap()) as usize;
+            (preview, body) = body.split_at(len.clamp(8, body.len()));
+        }
+
+        if body.starts_with(b"HEAD") {
+            self.check("decompressed size", body.len(), self.max_decompressed_size)?;
+            self.check_chunks(body)?;
+            return Ok(None);
+        }
+        let max = self.max_decompressed_size;
+        let raw = match miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(body, max) {
+            Ok(raw) => raw,
+            Err(err) if err.status == miniz_oxide::inflate::TINFLStatus::HasMoreOutput => {
+                return self
+                    .check("decompressed size", max.saturating_add(1), max)
+                    .map(|_| None);
+            }
+            // Not ours to report, the decoder will.
+            Err(_) => return Ok(None),
+        };
+        self.check_chunks(&raw)?;
+
+        let len = u32::try_from(8 + preview.len() + raw.len()).map_err(|_| ErrorKind::BadFile)?;
+        let mut file = Vec::with_capacity(len as _);
+        file.extend_from_slice(b"3DMO");
+        file.extend_from_slice(&len.to_le_bytes());
+        file.extend_from_slice(preview);
+        file.extend_from_slice(&raw);
+        Ok(Some(file))
+    }
+
+    fn check_chunks(&self, raw: &[u8]) -> Result<(), Error> {
+        let header = match chunk(raw) {
+            Some((b"HEAD", header, _)) if header.len() >= 8 => header,
+            _ => return Ok(()),
+        };
+        let s = Sizes::from_types(u32::from_le_bytes(header[4..8].try_into().unwrap()));
+
+        let (mut frames, mut transforms) = (0usize, 0usize);
+        let mut voxels = 0usize;
+        let mut rest = &raw[8 + header.len()..];
+        while let Some((magic, payload, next)) = chunk(rest) {
+            let name = String::from_utf8_lossy(magic);
+            let result = match magic {
+                b"VRTS" => {
+                    let reclen = s.ci as usize + s.sk as usize + 4 * s.vc as usize;
+                    self.check("vertices", payload.len() / reclen, self.max_vertices)
+                }
+                b"MESH" => self.check("faces", count_faces(payload, &s), self.max_faces),
+                b"BONE" => {
+                    let numbone = index(payload, s.bi).unwrap_or(0);
+                    let numskin = payload.get(s.bi as usize..).and_then(|p| index(p, s.sk));
+                    let numskin = numskin.unwrap_or(0);
+                    self.check("bones", numbone, self.max_bones)
+                        .and_then(|_| self.check("skins", numskin, self.max_skins))
+                }
+                b"ACTN" => {
+                    let numframe = payload.get(s.si as usize..).and_then(|p| index(p, 2));
+                    frames = frames.saturating_add(numframe.unwrap_or(0));
+                    transforms = transforms.saturating_add(count_transforms(payload, &s));
+                    self.check("frames", frames, self.max_frames)
+                        .and_then(|_| self.check("transforms", transforms, self.max_transforms))
+                }
+                b"VOXD" => {
+                    // The name and position come before the size.
+                    let size = s.si as usize + 3 * s.vd as usize;
+                    let count = (0..3)
+                        .map(|i| {
+                            payload
+                                .get(size + i * s.vd as usize..)
+                                .and_then(|p| index(p, s.vd))
+                        })
+                        .fold(1usize, |count, dim| count.saturating_mul(dim.unwrap_or(0)));
+                    voxels = voxels.saturating_add(count);
+                    self.check("voxels", voxels, self.max_voxels)
+                }
+                b"ASET" => self.check_png(payload.get(s.si as usize..).unwrap_or_default()),
+                _ => Ok(()),
+            };
+            result.map_err(|err| err.with_chunk(&name))?;
+            rest = next;
+        }
+        Ok(())
+    }
+}
+
+fn chunk(data: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    let len = u32::from_le_bytes(data.get(4..8)?.try_into().unwrap()) as usize;
+    if len < 8 || len > data.len() || data.starts_with(b"OMD3") {
+        return None;
+    }
+    Some((&data[..4], &data[8..len], &data[len..]))
+}
+
+fn index(data: &[u8], size: u8) -> Option<usize> {
+    let bytes = data.get(..size as usize)?;
+    Some(bytes.iter().rev().fold(0, |acc, &b| acc << 8 | b as usize))
+}
+
+// Transforms of all frames, walking the frame records up to where the chunk ends.
+fn count_transforms(data: &[u8], s: &Sizes) -> usize {
+    let numframe = data.get(s.si as usize..).and_then(|p| index(p, 2));
+    let mut frame = data.get(s.si as usize + 6..).unwrap_or_default();
+    let transform = s.bi as usize + 2 * s.vi as usize;
+    let mut transforms = 0usize;
+    for _ in 0..numframe.unwrap_or(0) {
+        let numtransform = match frame.get(4..).and_then(|p| index(p, s.fc)) {
+            Some(n) => n,
+            None => break,
+        };
+        transforms = transforms.saturating_add(numtransform);
+        let len = numtransform.saturating_mul(transform);
+        frame = frame.get(4 + s.fc as usize + len..).unwrap_or_default();
+    }
+    transforms
+}
+
+// Same record layout as the decoder's `mesh`, without keeping anything.
+fn count_faces(mut data: &[u8], s: &Sizes) -> usize {
+    let mut faces = 0;
+    while let Some((&k, rest)) = data.split_first() {
+        let (n, k) = (k >> 4, k & 15);
+        let len = match n {
+            0 => s.si as usize,
+            3 => {
+                let mut corner = s.vi as usize;
+                if k & 1 != 0 {
+                    corner += s.ti as usize;
+                }
+                if k & 2 != 0 {
+                    corner += s.vi as usize;
+                }
+                if k & 4 != 0 {
+                    corner += s.vi as usize;
+                }
+                faces += 1;
+                3 * corner
+            }
+            _ => break,
+        };
+        data = rest.get(len..).unwrap_or_default();
+    }
+    faces
+}
@@ -0,0 +1,199 @@
+use super::*;
+use std::cell::Cell;
+use std::ptr::NonNull;
+
+impl LoadContext<'_> {
+    pub(super) fn load_sdk(
+        &self,
+        data: &[u8],
+        mtllib: Option<Model>,
+        ignore_inlined: bool,
+    ) -> Result<Model, Error> {
+        // The ASCII parser expects a zero terminated buffer.
+        let mut data_c = Vec::with_capacity(data.len() + 1);
+        data_c.extend_from_slice(data);
+        data_c.push(0);
+
+        let mtllib = mtllib.map(Box::new);
+        let mtllib_c = match &mtllib {
+            Some(mtl) => mtl.as_raw(),
+            None => std::ptr::null_mut(),
+        };
+
+        // The C callbacks take no user pointer, they find the context in a thread local.
+        let m3d_c = self.enter(|| unsafe {
+            m3dc::m3d_load(
+                data_c.as_mut_ptr(),
+                Some(m3dread_context),
+                Some(m3dfree_default),
+                mtllib_c,
+            )
+        });
+
+        let raw = NonNull::new(m3d_c).ok_or(ErrorKind::ReturnedNull)?;
+        let m = unsafe { &mut *raw.as_ptr() };
+        if !self.generate_normals {
+            drop_generated_normals(m);
+        }
+        if ignore_inlined {
+            // The entries point into the model's raw buffer, which m3d_free releases on its own.
+            m.numinlined = 0;
+        }
+        let strings = self.strings.take();
+        Ok(unsafe { Model::from_raw(raw, data_c, mtllib, strings) })
+    }
+}
+
+// The SDK always generates normals, appending one per vertex and doubling `numvertex`.
+fn drop_generated_normals(m: &mut m3dc::m3d_t) {
+    if m.flags as u32 & m3dc::M3D_FLG_GENNORM == 0 {
+        return;
+    }
+
+    let n = m.numvertex / 2;
+    if !m.face.is_null() {
+        let faces = unsafe { std::slice::from_raw_parts_mut(m.face, m.numface as _) };
+        for face in faces {
+            if (0..3).all(|j| face.normal[j] == face.vertex[j].wrapping_add(n)) {
+                face.normal = [m3dc::M3D_UNDEF as _; 3];
+            }
+        }
+    }
+    m.numvertex = n;
+    m.flags &= !(m3dc::M3D_FLG_GENNORM as libc::c_char);
+}
+
+thread_local! {
+    static CONTEXT: Cell<*const LoadContext<'static>> = const { Cell::new(std::ptr::null()) };
+}
+
+impl LoadContext<'_> {
+    fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(*const LoadContext<'static>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CONTEXT.with(|c| c.set(self.0));
+            }
+        }
+
+        let this = (self as *const Self).cast::<LoadContext<'static>>();
+        let _restore = Restore(CONTEXT.with(|c| c.replace(this)));
+        f()
+    }
+
+    fn with_current<R>(f: impl FnOnce(&LoadContext<'_>) -> R) -> Option<R> {
+        let current = CONTEXT.with(|c| c.get());
+        if current.is_null() {
+            return None;
+        }
+
+        // Callbacks must not unwind into the C loader.
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(unsafe { &*current }))).ok()
+    }
+}
+
+unsafe fn malloc_copy(data: &[u8]) -> *mut u8 {
+    // Keep a trailing zero, text assets are parsed as C strings.
+    let ptr = alloc::malloc(data.len() + 1) as *mut u8;
+    if !ptr.is_null() {
+        std::ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+        *ptr.add(data.len()) = 0;
+    }
+    ptr
+}
+
+unsafe extern "C" fn m3dread_context(
+    filename: *mut libc::c_char,
+    size: *mut libc::c_uint,
+) -> *mut libc::c_uchar {
+    *size = 0;
+    if filename.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let name = cptr_to_str(filename);
+    let data = LoadContext::with_current(|ctx| ctx.resolve(name)).flatten();
+
+    match data {
+        Some(data) if data.len() < u32::MAX as usize => {
+            let ptr = malloc_copy(&data);
+            if !ptr.is_null() {
+                *size = data.len() as _;
+            }
+            ptr
+        }
+        _ => std::ptr::null_mut(),
+    }
+}
+
+// Compiled into the C SDK as `M3D_TX_INTERP`, see build.rs.
+#[no_mangle]
+unsafe extern "C" fn m3d_rs_txsc(
+    name: *const libc::c_char,
+    script: *const libc::c_void,
+    len: u32,
+    output: *mut m3dc::m3dtx_t,
+) -> libc::c_int {
+    if name.is_null() || output.is_null() {
+        return m3dc::M3D_ERR_UNKIMG;
+    }
+
+    let name = cptr_to_str(name);
+    let script = cptr_to_slice(script as *const u8, len as _);
+    let texture = LoadContext::with_current(|ctx| {
+        ctx.texture_script
+            .and_then(|handler| handler.generate(name, script))
+            .filter(|t| ctx.allow_texture(name, t.width, t.height))
+    })
+    .flatten();
+
+    match texture {
+        Some(texture) if texture.is_valid() => {
+            let pixels = malloc_copy(&texture.pixels);
+            if pixels.is_null() {
+                return m3dc::M3D_ERR_ALLOC;
+            }
+            (*output).w = texture.width;
+            (*output).h = texture.height;
+            (*output).f = texture.format as _;
+            (*output).d = pixels;
+            m3dc::M3D_SUCCESS as _
+        }
+        _ => m3dc::M3D_ERR_UNKIMG,
+    }
+}
+
+const _: m3dc::m3dtxsc_t = Some(m3d_rs_txsc);
+
+// Compiled into the C SDK as `M3D_PR_INTERP`, see build.rs.
+#[no_mangle]
+unsafe extern "C" fn m3d_rs_prsc(
+    name: *const libc::c_char,
+    script: *const libc::c_void,
+    len: u32,
+    model: *mut m3dc::m3d_t,
+) -> libc::c_int {
+    if name.is_null() || model.is_null() {
+        return m3dc::M3D_ERR_UNKMESH;
+    }
+
+    let name = cptr_to_str(name);
+    let script = cptr_to_slice(script as *const u8, len as _);
+    let result = LoadContext::with_current(|ctx| {
+        let handler = ctx.procedural.ok_or(ErrorKind::Unimplemented)?;
+        let mut strings = ctx.strings.borrow_mut();
+        handler.generate(
+            name,
+            script,
+            &mut ProceduralModel::new(&mut *model, &mut strings),
+        )
+    });
+
+    match result {
+        Some(Ok(())) => m3dc::M3D_SUCCESS as _,
+        Some(Err(err)) => err.kind() as _,
+        None => m3dc::M3D_ERR_UNKMESH,
+    }
+}
+
+const _: m3dc::m3dprsc_t = Some(m3d_rs_prsc);
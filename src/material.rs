@@ -14,4 +14,256 @@ impl Material {
     pub fn props(&self) -> &[MaterialProp] {
         unsafe { cptr_to_slice(self.0.prop, self.0.numprop as _) }
     }
+
+    pub fn properties(&self) -> impl Iterator<Item = MaterialProperty> + '_ {
+        self.props().iter().map(MaterialProperty::from)
+    }
+
+    fn prop(&self, type_: u8) -> Option<&MaterialProp> {
+        self.props().iter().find(|p| p.type_ == type_)
+    }
+
+    fn color(&self, kind: PropertyKind) -> Option<Color> {
+        self.prop(kind as u8)
+            .map(|p| Color(unsafe { p.value.color }))
+    }
+
+    fn float(&self, kind: PropertyKind) -> Option<f32> {
+        self.prop(kind as u8).map(|p| unsafe { p.value.fnum })
+    }
+
+    /// The texture a property is mapped to, as an index into the model's textures.
+    pub fn map(&self, kind: PropertyKind) -> Option<Index> {
+        self.prop(kind as u8 + 128)
+            .map(|p| unsafe { p.value.textureid })
+    }
+
+    pub fn diffuse(&self) -> Option<Color> {
+        self.color(PropertyKind::Diffuse)
+    }
+
+    pub fn ambient(&self) -> Option<Color> {
+        self.color(PropertyKind::Ambient)
+    }
+
+    pub fn specular(&self) -> Option<Color> {
+        self.color(PropertyKind::Specular)
+    }
+
+    pub fn specular_exponent(&self) -> Option<f32> {
+        self.float(PropertyKind::SpecularExponent)
+    }
+
+    pub fn emissive(&self) -> Option<Color> {
+        self.color(PropertyKind::Emissive)
+    }
+
+    pub fn transmission(&self) -> Option<Color> {
+        self.color(PropertyKind::Transmission)
+    }
+
+    pub fn bump_strength(&self) -> Option<f32> {
+        self.float(PropertyKind::BumpStrength)
+    }
+
+    pub fn dissolve(&self) -> Option<f32> {
+        self.float(PropertyKind::Dissolve)
+    }
+
+    pub fn illumination_model(&self) -> Option<u32> {
+        self.prop(PropertyKind::IlluminationModel as u8)
+            .map(|p| unsafe { p.value.num })
+    }
+
+    pub fn roughness(&self) -> Option<f32> {
+        self.float(PropertyKind::Roughness)
+    }
+
+    pub fn metallic(&self) -> Option<f32> {
+        self.float(PropertyKind::Metallic)
+    }
+
+    pub fn sheen(&self) -> Option<f32> {
+        self.float(PropertyKind::Sheen)
+    }
+
+    pub fn refraction_index(&self) -> Option<f32> {
+        self.float(PropertyKind::RefractionIndex)
+    }
+
+    pub fn thickness(&self) -> Option<f32> {
+        self.float(PropertyKind::Thickness)
+    }
+
+    /// `bump`, the bump strength map.
+    pub fn bump(&self) -> Option<Index> {
+        self.map(PropertyKind::BumpStrength)
+    }
+
+    /// `refl`, the metallic map.
+    pub fn refl(&self) -> Option<Index> {
+        self.map(PropertyKind::Metallic)
+    }
+
+    /// `map_il`, which is also `map_N`, the normal map.
+    pub fn map_il(&self) -> Option<Index> {
+        self.map(PropertyKind::IlluminationModel)
+    }
+}
+
+/// Scalar material properties. Any of them can also be a texture map.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, num_enum::TryFromPrimitive)]
+#[repr(u8)]
+pub enum PropertyKind {
+    Diffuse = m3dc::m3dp_Kd as _,
+    Ambient = m3dc::m3dp_Ka as _,
+    Specular = m3dc::m3dp_Ks as _,
+    SpecularExponent = m3dc::m3dp_Ns as _,
+    Emissive = m3dc::m3dp_Ke as _,
+    Transmission = m3dc::m3dp_Tf as _,
+    BumpStrength = m3dc::m3dp_Km as _,
+    Dissolve = m3dc::m3dp_d as _,
+    IlluminationModel = m3dc::m3dp_il as _,
+    Roughness = m3dc::m3dp_Pr as _,
+    Metallic = m3dc::m3dp_Pm as _,
+    Sheen = m3dc::m3dp_Ps as _,
+    RefractionIndex = m3dc::m3dp_Ni as _,
+    Thickness = m3dc::m3dp_Nt as _,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PropertyValue {
+    Color(Color),
+    Float(f32),
+    Int(u32),
+    /// Index into the model's textures.
+    Texture(u32),
+}
+
+impl PropertyValue {
+    // The value as stored for `kind`, maps are checked against the model's `textures`.
+    pub(crate) fn to_prop(
+        self,
+        kind: PropertyKind,
+        textures: usize,
+    ) -> Result<MaterialProp, Error> {
+        let format = native::property_format(kind as u8).ok_or(ErrorKind::UnkownProperty)?;
+
+        let mut prop = MaterialProp {
+            type_: kind as u8,
+            value: unsafe { std::mem::zeroed() },
+        };
+        match self {
+            PropertyValue::Color(color) if format == native::PF_COLOR => {
+                prop.value.color = color.into()
+            }
+            PropertyValue::Float(fnum) if format == native::PF_FLOAT => prop.value.fnum = fnum,
+            PropertyValue::Int(num) if format == native::PF_UINT8 && num <= u8::MAX as u32 => {
+                prop.value.num = num
+            }
+            PropertyValue::Texture(texture) if (texture as usize) < textures => {
+                prop.type_ += 128;
+                prop.value.textureid = texture as _;
+            }
+            PropertyValue::Texture(_) => return Err(ErrorKind::UnknownImg.into()),
+            _ => return Err(ErrorKind::UnkownProperty.into()),
+        }
+        Ok(prop)
+    }
+}
+
+/// A decoded [`MaterialProp`]. Maps hold an index into the model's textures.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MaterialProperty {
+    DiffuseColor(Color),
+    AmbientColor(Color),
+    SpecularColor(Color),
+    SpecularExponent(f32),
+    EmissiveColor(Color),
+    TransmissionColor(Color),
+    BumpStrength(f32),
+    Dissolve(f32),
+    IlluminationModel(u32),
+    Roughness(f32),
+    Metallic(f32),
+    Sheen(f32),
+    RefractionIndex(f32),
+    Thickness(f32),
+    DiffuseMap(Index),
+    AmbientMap(Index),
+    SpecularMap(Index),
+    SpecularExponentMap(Index),
+    EmissiveMap(Index),
+    TransmissionMap(Index),
+    /// `map_Km`, also written `bump`.
+    BumpMap(Index),
+    DissolveMap(Index),
+    /// `map_il`, also written `map_N`.
+    NormalMap(Index),
+    RoughnessMap(Index),
+    /// `map_Pm`, also written `refl`.
+    MetallicMap(Index),
+    SheenMap(Index),
+    RefractionIndexMap(Index),
+    ThicknessMap(Index),
+    /// A property this crate doesn't know, with its raw value.
+    Unknown {
+        kind: u8,
+        value: u32,
+    },
+}
+
+impl From<&MaterialProp> for MaterialProperty {
+    fn from(prop: &MaterialProp) -> Self {
+        use MaterialProperty::*;
+
+        let kind = match PropertyKind::try_from(prop.type_ & 127) {
+            Ok(kind) => kind,
+            Err(_) => {
+                return Unknown {
+                    kind: prop.type_,
+                    value: unsafe { prop.value.num },
+                }
+            }
+        };
+        if prop.type_ >= 128 {
+            let texture = unsafe { prop.value.textureid };
+            return match kind {
+                PropertyKind::Diffuse => DiffuseMap(texture),
+                PropertyKind::Ambient => AmbientMap(texture),
+                PropertyKind::Specular => SpecularMap(texture),
+                PropertyKind::SpecularExponent => SpecularExponentMap(texture),
+                PropertyKind::Emissive => EmissiveMap(texture),
+                PropertyKind::Transmission => TransmissionMap(texture),
+                PropertyKind::BumpStrength => BumpMap(texture),
+                PropertyKind::Dissolve => DissolveMap(texture),
+                PropertyKind::IlluminationModel => NormalMap(texture),
+                PropertyKind::Roughness => RoughnessMap(texture),
+                PropertyKind::Metallic => MetallicMap(texture),
+                PropertyKind::Sheen => SheenMap(texture),
+                PropertyKind::RefractionIndex => RefractionIndexMap(texture),
+                PropertyKind::Thickness => ThicknessMap(texture),
+            };
+        }
+
+        let value = prop.value;
+        unsafe {
+            match kind {
+                PropertyKind::Diffuse => DiffuseColor(Color(value.color)),
+                PropertyKind::Ambient => AmbientColor(Color(value.color)),
+                PropertyKind::Specular => SpecularColor(Color(value.color)),
+                PropertyKind::SpecularExponent => SpecularExponent(value.fnum),
+                PropertyKind::Emissive => EmissiveColor(Color(value.color)),
+                PropertyKind::Transmission => TransmissionColor(Color(value.color)),
+                PropertyKind::BumpStrength => BumpStrength(value.fnum),
+                PropertyKind::Dissolve => Dissolve(value.fnum),
+                PropertyKind::IlluminationModel => IlluminationModel(value.num),
+                PropertyKind::Roughness => Roughness(value.fnum),
+                PropertyKind::Metallic => Metallic(value.fnum),
+                PropertyKind::Sheen => Sheen(value.fnum),
+                PropertyKind::RefractionIndex => RefractionIndex(value.fnum),
+                PropertyKind::Thickness => Thickness(value.fnum),
+            }
+        }
+    }
 }
@@ -0,0 +1,106 @@
+use super::*;
+#[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+use std::ffi::CString;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+/// Owned model, released through `m3d_free` or its Rust exporter on drop.
+#[derive(Debug)]
+pub struct Model {
+    raw: NonNull<m3dc::m3d_t>,
+    owner: Owner,
+    // How many material libraries this model was loaded against, see `Limits::max_mtllib_depth`.
+    pub(crate) mtllib_depth: usize,
+}
+
+#[derive(Debug)]
+enum Owner {
+    // Loaded by the C SDK.
+    #[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+    Sdk {
+        // Binary models keep pointers into the source buffer, so it has to outlive `raw`.
+        _data: Vec<u8>,
+        // Models loaded against a material library share its materials and textures.
+        _mtllib: Option<Box<Model>>,
+        // Strings added by procedurals when the model doesn't own its strings.
+        _strings: Vec<CString>,
+    },
+    // Built from a `ModelData`, every allocation is owned by `raw`.
+    #[cfg(not(any(feature = "double", feature = "small-index")))]
+    Exported,
+}
+
+// The model exclusively owns every C allocation it points to.
+unsafe impl Send for Model {}
+
+impl Drop for Model {
+    fn drop(&mut self) {
+        match self.owner {
+            #[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+            Owner::Sdk { .. } => unsafe { m3dc::m3d_free(self.raw.as_ptr()) },
+            #[cfg(not(any(feature = "double", feature = "small-index")))]
+            Owner::Exported => unsafe { data::free_exported(self.raw.as_ptr()) },
+        }
+    }
+}
+
+impl Deref for Model {
+    type Target = Obj;
+
+    fn deref(&self) -> &Obj {
+        unsafe { &*(self.raw.as_ptr() as *const Obj) }
+    }
+}
+
+impl Model {
+    /// Loads a model from bytes, a file or a reader.
+    pub fn load<'s>(
+        source: impl Into<Source<'s>>,
+        options: LoadOptions,
+    ) -> Result<LoadReport, Error> {
+        options.load_source(source.into())
+    }
+
+    #[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+    pub(crate) unsafe fn from_raw(
+        raw: NonNull<m3dc::m3d_t>,
+        data: Vec<u8>,
+        mtllib: Option<Box<Model>>,
+        strings: Vec<CString>,
+    ) -> Self {
+        Self {
+            raw,
+            owner: Owner::Sdk {
+                _data: data,
+                _mtllib: mtllib,
+                _strings: strings,
+            },
+            mtllib_depth: 0,
+        }
+    }
+
+    #[cfg(not(any(feature = "double", feature = "small-index")))]
+    pub(crate) unsafe fn from_exported(raw: NonNull<m3dc::m3d_t>) -> Self {
+        Self {
+            raw,
+            owner: Owner::Exported,
+            mtllib_depth: 0,
+        }
+    }
+
+    #[cfg(all(not(feature = "pure-rust"), feature = "importer"))]
+    pub(crate) fn as_raw(&self) -> *mut m3dc::m3d_t {
+        self.raw.as_ptr()
+    }
+
+    #[cfg(not(feature = "pure-rust"))]
+    pub(crate) fn errcode(&self) -> i8 {
+        unsafe { self.raw.as_ref().errcode }
+    }
+
+    #[cfg(feature = "pure-rust")]
+    pub(crate) fn set_errcode(&self, errcode: Option<ErrorKind>) {
+        let errcode = errcode.map_or(m3dc::M3D_SUCCESS as i8, |err| err as i8);
+        unsafe { (*self.raw.as_ptr()).errcode = errcode }
+    }
+}
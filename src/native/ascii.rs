@@ -0,0 +1,1011 @@
+use super::*;
+use std::fmt::Write;
+
+/// Parsed text model, with the references which need a resolver left by name.
+pub(crate) struct Text {
+    pub model: ModelData,
+    pub procedurals: Vec<String>,
+    pub preview: Option<String>,
+    /// What the SDK skips over, like unknown sections and materials.
+    pub warnings: Vec<Error>,
+}
+
+#[derive(Copy, Clone)]
+struct Line<'a> {
+    no: usize,
+    text: &'a str,
+}
+
+#[derive(Copy, Clone)]
+struct Token<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    fn err(&self, column: usize, message: &'static str) -> SyntaxError {
+        SyntaxError {
+            line: self.no,
+            column,
+            message,
+        }
+    }
+
+    // The first `n` whitespace separated tokens and the rest of the line.
+    fn split(&self, n: usize) -> (Vec<Token<'a>>, &'a str) {
+        let mut tokens = Vec::new();
+        let mut rest = self.text;
+        let mut offset = 0;
+        while tokens.len() < n {
+            let trimmed = rest.trim_start();
+            offset += rest.len() - trimmed.len();
+            if trimmed.is_empty() {
+                break;
+            }
+            let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+            tokens.push(Token {
+                line: self.no,
+                column: offset + 1,
+                text: &trimmed[..end],
+            });
+            offset += end;
+            rest = &trimmed[end..];
+        }
+        (tokens, rest.trim())
+    }
+
+    fn tokens(&self) -> Vec<Token<'a>> {
+        self.split(usize::MAX).0
+    }
+
+    fn fields(&self, n: usize) -> Result<(Vec<Token<'a>>, &'a str), SyntaxError> {
+        let (tokens, rest) = self.split(n);
+        if tokens.len() < n {
+            return Err(self.err(self.text.len() + 1, "missing value"));
+        }
+        Ok((tokens, rest))
+    }
+}
+
+impl<'a> Token<'a> {
+    fn err(&self, message: &'static str) -> SyntaxError {
+        SyntaxError {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+
+    fn float(&self) -> Result<f32, SyntaxError> {
+        self.text.parse().map_err(|_| self.err("expected a number"))
+    }
+
+    // Like narrow binary indices, -1 and -2 stand for the two topmost values.
+    fn idx(&self) -> Result<u32, SyntaxError> {
+        match self.text.parse::<i64>() {
+            Ok(v) if (-2..=u32::MAX as i64).contains(&v) => Ok(v as u32),
+            _ => Err(self.err("expected an index")),
+        }
+    }
+
+    fn signed(&self) -> Result<i32, SyntaxError> {
+        self.text
+            .parse()
+            .map_err(|_| self.err("expected an integer"))
+    }
+
+    fn byte(&self) -> Result<u8, SyntaxError> {
+        self.text.parse().map_err(|_| self.err("expected a byte"))
+    }
+
+    fn color(&self) -> Result<u32, SyntaxError> {
+        self.text
+            .strip_prefix('#')
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| self.err("expected a #hex color"))
+    }
+
+    fn check(&self, idx: u32, len: usize, message: &'static str) -> Result<u32, SyntaxError> {
+        if idx == m3dc::M3D_UNDEF || (idx as usize) < len {
+            Ok(idx)
+        } else {
+            Err(self.err(message))
+        }
+    }
+}
+
+pub(crate) fn parse(text: &str, model: ModelData) -> Result<Text, SyntaxError> {
+    let lines: Vec<Line> = text
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Line { no: i + 1, text })
+        .collect();
+    let mut parser = Parser {
+        text: Text {
+            model,
+            procedurals: Vec::new(),
+            preview: None,
+            warnings: Vec::new(),
+        },
+    };
+
+    let magic = lines.first().copied().unwrap_or(Line { no: 1, text: "" });
+    let (tokens, _) = magic.split(2);
+    if tokens.first().map(|t| t.text) != Some("3dmodel") {
+        return Err(magic.err(1, "expected 3dmodel"));
+    }
+    let model = &mut parser.text.model;
+    model.scale = match tokens.get(1) {
+        Some(scale) => scale.float()?,
+        None => 1.0,
+    };
+
+    // The header lines are positional, the description runs until the first blank line.
+    let header = |i: usize| lines.get(i).map_or("", |l| l.text.trim());
+    model.name = header(1).to_string();
+    model.license = header(2).to_string();
+    model.author = header(3).to_string();
+    let mut i = 4.min(lines.len());
+    let desc_len = lines[i..]
+        .iter()
+        .take_while(|l| !l.text.trim().is_empty())
+        .count();
+    model.desc = lines[i..i + desc_len]
+        .iter()
+        .map(|l| l.text.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+    i += desc_len;
+
+    while i < lines.len() {
+        if lines[i].text.trim().is_empty() {
+            i += 1;
+            continue;
+        }
+        // The SDK closes its files with `End`, whatever follows is not read.
+        if lines[i].text.trim() == "End" {
+            break;
+        }
+        let len = lines[i..]
+            .iter()
+            .take_while(|l| !l.text.trim().is_empty())
+            .count();
+        parser.section(lines[i], &lines[i + 1..i + len])?;
+        i += len;
+    }
+    Ok(parser.text)
+}
+
+struct Parser {
+    text: Text,
+}
+
+impl Parser {
+    fn section(&mut self, head: Line, body: &[Line]) -> Result<(), SyntaxError> {
+        let (keyword, rest) = head.fields(1)?;
+        match keyword[0].text {
+            "Preview" => {
+                self.text.preview = body.first().map(|l| l.text.trim().to_string());
+                Ok(())
+            }
+            "Textmap" => self.texture_maps(body),
+            "Vertex" => self.vertices(body),
+            "Bones" => self.bones(body),
+            "Material" => self.material(rest, head, body),
+            "Procedural" => {
+                let names = body.iter().map(|l| l.text.trim().to_string());
+                self.text.procedurals.extend(names);
+                Ok(())
+            }
+            "Mesh" => self.mesh(body),
+            "Shape" => self.shape(rest, body),
+            "Labels" => self.labels(rest, body),
+            "Action" => self.action(head, body),
+            "VoxTypes" => self.voxel_types(body),
+            "Voxel" => self.voxel_block(rest, head, body),
+            section => {
+                let err = keyword[0].err("unknown section");
+                self.text.warnings.push(
+                    Error::from(ErrorKind::Unimplemented)
+                        .with_chunk(section)
+                        .with_syntax(err),
+                );
+                Ok(())
+            }
+        }
+    }
+
+    // Like the binary reader, an unknown material is left undefined with a warning.
+    fn material_id(&mut self, token: &Token) -> u32 {
+        if token.text == "-" {
+            return m3dc::M3D_UNDEF;
+        }
+        let id = self
+            .text
+            .model
+            .materials
+            .iter()
+            .position(|m| m.name == token.text);
+        match id {
+            Some(id) => id as _,
+            None => {
+                let err = token.err("unknown material");
+                let warning = Error::from(ErrorKind::Material).with_syntax(err);
+                self.text.warnings.push(warning);
+                m3dc::M3D_UNDEF
+            }
+        }
+    }
+
+    fn texture_maps(&mut self, body: &[Line]) -> Result<(), SyntaxError> {
+        for line in body {
+            let (t, _) = line.fields(2)?;
+            self.text.model.texture_maps.push(TextureMapIndex {
+                u: t[0].float()?,
+                v: t[1].float()?,
+            });
+        }
+        Ok(())
+    }
+
+    fn vertices(&mut self, body: &[Line]) -> Result<(), SyntaxError> {
+        let model = &mut self.text.model;
+        for line in body {
+            let tokens = line.tokens();
+            if tokens.len() < 3 {
+                return Err(line.err(line.text.len() + 1, "missing value"));
+            }
+
+            let mut vertex = Vertex {
+                x: tokens[0].float()?,
+                y: tokens[1].float()?,
+                z: tokens[2].float()?,
+                w: 1.0,
+                color: 0,
+                skinid: m3dc::M3D_UNDEF,
+            };
+            let mut rest = &tokens[3..];
+            if let Some(w) = rest
+                .first()
+                .filter(|t| !t.text.starts_with('#') && !t.text.contains(':'))
+            {
+                vertex.w = w.float()?;
+                rest = &rest[1..];
+            }
+            if let Some(color) = rest.first().filter(|t| t.text.starts_with('#')) {
+                vertex.color = color.color()?;
+                rest = &rest[1..];
+            }
+
+            // Bone weights as `boneid:weight` pairs, the SDK writes a single full weight as
+            // the bone id alone.
+            if !rest.is_empty() {
+                if rest.len() > m3dc::M3D_NUMBONE as usize {
+                    return Err(rest[m3dc::M3D_NUMBONE as usize].err("too many bone weights"));
+                }
+                let mut skin = Skin {
+                    boneid: [m3dc::M3D_UNDEF; m3dc::M3D_NUMBONE as usize],
+                    weight: [0.0; m3dc::M3D_NUMBONE as usize],
+                };
+                for (j, token) in rest.iter().enumerate() {
+                    let (boneid, weight) = match token.text.split_once(':') {
+                        Some((boneid, weight)) => (boneid, Some(weight)),
+                        None if j == 0 => (token.text, None),
+                        None => return Err(token.err("expected boneid:weight")),
+                    };
+                    skin.boneid[j] = Token {
+                        text: boneid,
+                        ..*token
+                    }
+                    .idx()?;
+                    skin.weight[j] = match weight {
+                        Some(weight) => Token {
+                            text: weight,
+                            ..*token
+                        }
+                        .float()?,
+                        None => 1.0,
+                    };
+                }
+                vertex.skinid = model.skins.len() as _;
+                model.skins.push(skin);
+            }
+            model.vertices.push(vertex);
+        }
+        Ok(())
+    }
+
+    fn bones(&mut self, body: &[Line]) -> Result<(), SyntaxError> {
+        let model = &mut self.text.model;
+        // Each leading slash is one level deeper in the hierarchy.
+        let mut parents: Vec<u32> = Vec::new();
+        for line in body {
+            let level = line.text.len() - line.text.trim_start_matches('/').len();
+            let parent = match level {
+                0 => m3dc::M3D_UNDEF,
+                _ => *parents
+                    .get(level - 1)
+                    .ok_or_else(|| line.err(1, "bone has no parent"))?,
+            };
+
+            let (t, name) = Line {
+                no: line.no,
+                text: &line.text[level..],
+            }
+            .fields(2)?;
+            let numvertex = model.vertices.len();
+            let id = model.bones.len() as u32;
+            model.bones.push(BoneData {
+                parent,
+                name: name.to_string(),
+                position: t[0].check(t[0].idx()?, numvertex, "unknown vertex")?,
+                orientation: t[1].check(t[1].idx()?, numvertex, "unknown vertex")?,
+                weights: Vec::new(),
+                mat4: [0.0; 16],
+            });
+            parents.truncate(level);
+            parents.push(id);
+        }
+        Ok(())
+    }
+
+    fn material(&mut self, name: &str, head: Line, body: &[Line]) -> Result<(), SyntaxError> {
+        let model = &mut self.text.model;
+        if model.materials.iter().any(|m| m.name == name) {
+            return Err(head.err(1, "duplicate material"));
+        }
+
+        let mut material = MaterialData {
+            name: name.to_string(),
+            props: Vec::new(),
+        };
+        for line in body {
+            let (t, rest) = line.fields(1)?;
+            let (type_, format) =
+                property_by_name(t[0].text).ok_or_else(|| t[0].err("unknown property"))?;
+            let value = Token {
+                line: line.no,
+                column: line.text.len() - line.text.trim_start().len() + t[0].text.len() + 2,
+                text: rest,
+            };
+            if rest.is_empty() {
+                return Err(value.err("missing value"));
+            }
+
+            let mut prop = MaterialProp {
+                type_,
+                value: unsafe { std::mem::zeroed() },
+            };
+            match format {
+                PF_COLOR => prop.value.color = value.color()?,
+                PF_UINT8 => prop.value.num = value.byte()? as _,
+                PF_UINT16 | PF_UINT32 => prop.value.num = value.idx()?,
+                PF_FLOAT => prop.value.fnum = value.float()?,
+                _ => {
+                    // Textures are resolved by the loader, only their names are known here.
+                    let id = match model.textures.iter().position(|t| t.name == rest) {
+                        Some(id) => id,
+                        None => {
+                            model.textures.push(TextureData {
+                                name: rest.to_string(),
+                                ..Default::default()
+                            });
+                            model.textures.len() - 1
+                        }
+                    };
+                    prop.value.textureid = id as _;
+                }
+            }
+            material.props.push(prop);
+        }
+
+        if material.props.len() > u8::MAX as usize {
+            return Err(head.err(1, "too many properties"));
+        }
+        model.materials.push(material);
+        Ok(())
+    }
+
+    fn mesh(&mut self, body: &[Line]) -> Result<(), SyntaxError> {
+        let mut materialid = m3dc::M3D_UNDEF;
+        for line in body {
+            let tokens = line.tokens();
+            if tokens[0].text == "use" {
+                materialid = match tokens.get(1) {
+                    Some(name) => self.material_id(name),
+                    None => m3dc::M3D_UNDEF,
+                };
+                continue;
+            }
+            if tokens.len() != 3 {
+                return Err(line.err(1, "expected three vertices"));
+            }
+
+            let model = &self.text.model;
+            let mut face = Face {
+                materialid,
+                vertex: [m3dc::M3D_UNDEF; 3],
+                normal: [m3dc::M3D_UNDEF; 3],
+                texcoord: [m3dc::M3D_UNDEF; 3],
+            };
+            // Each corner is `vertex[/texcoord[/normal]]`, empty parts are left undefined.
+            for (j, token) in tokens.iter().enumerate() {
+                let mut parts = token.text.split('/');
+                let mut part = |len: usize, message| match parts.next() {
+                    Some("") | None => Ok(m3dc::M3D_UNDEF),
+                    Some(text) => {
+                        let part = Token { text, ..*token };
+                        token.check(part.idx()?, len, message)
+                    }
+                };
+                face.vertex[j] = part(model.vertices.len(), "unknown vertex")?;
+                face.texcoord[j] = part(model.texture_maps.len(), "unknown texture map")?;
+                face.normal[j] = part(model.vertices.len(), "unknown normal")?;
+                if face.vertex[j] == m3dc::M3D_UNDEF {
+                    return Err(token.err("expected a vertex"));
+                }
+            }
+            self.text.model.faces.push(face);
+        }
+        Ok(())
+    }
+
+    fn shape(&mut self, name: &str, body: &[Line]) -> Result<(), SyntaxError> {
+        let mut shape = ShapeData {
+            name: name.to_string(),
+            group: m3dc::M3D_UNDEF,
+            commands: Vec::new(),
+        };
+        for line in body {
+            let tokens = line.tokens();
+            if tokens[0].text == "group" {
+                let group = tokens
+                    .get(1)
+                    .ok_or_else(|| line.err(line.text.len() + 1, "missing value"))?;
+                shape.group =
+                    group.check(group.idx()?, self.text.model.bones.len(), "unknown bone")?;
+                continue;
+            }
+
+            let kind = COMMAND_TYPES
+                .iter()
+                .position(|c| c.name == tokens[0].text)
+                .ok_or_else(|| tokens[0].err("unknown command"))?;
+            let def = &COMMAND_TYPES[kind];
+            let args = &tokens[1..];
+
+            // Same walk over the argument types as the binary reader.
+            let p = def.args.len();
+            let mut values = Vec::with_capacity(args.len());
+            let (mut k, mut n, mut l) = (0, 0, p);
+            while k < l {
+                let token = args
+                    .get(k)
+                    .ok_or_else(|| line.err(line.text.len() + 1, "missing argument"))?;
+                let value = match def.args[((k - n) % (p - n)) + n] {
+                    MI => self.material_id(token),
+                    VC => token.float()?.to_bits(),
+                    VA => {
+                        let count = token.idx()? as usize;
+                        if count == 0 || count > args.len() {
+                            return Err(token.err("bad argument count"));
+                        }
+                        n = k + 1;
+                        l += (count - 1) * (p - k - 1);
+                        count as u32
+                    }
+                    _ => token.idx()?,
+                };
+                values.push(value);
+                k += 1;
+            }
+            if let Some(extra) = args.get(l) {
+                return Err(extra.err("too many arguments"));
+            }
+
+            shape.commands.push(ShapeCommandData {
+                kind: kind as _,
+                args: values,
+            });
+        }
+        self.text.model.shapes.push(shape);
+        Ok(())
+    }
+
+    fn labels(&mut self, name: &str, body: &[Line]) -> Result<(), SyntaxError> {
+        let model = &mut self.text.model;
+        let (mut lang, mut color) = (String::new(), 0);
+        for line in body {
+            let (t, rest) = line.fields(1)?;
+            match t[0].text {
+                "lang" => lang = rest.to_string(),
+                "color" => {
+                    let (t, _) = line.fields(2)?;
+                    color = t[1].color()?;
+                }
+                _ => model.labels.push(LabelData {
+                    name: name.to_string(),
+                    lang: lang.clone(),
+                    text: rest.to_string(),
+                    color,
+                    vertex_id: t[0].check(t[0].idx()?, model.vertices.len(), "unknown vertex")?,
+                }),
+            }
+        }
+        Ok(())
+    }
+
+    fn action(&mut self, head: Line, body: &[Line]) -> Result<(), SyntaxError> {
+        let model = &mut self.text.model;
+        let (t, name) = head.fields(2)?;
+        let mut action = ActionData {
+            name: name.to_string(),
+            duration_msec: t[1].idx()?,
+            frames: Vec::new(),
+        };
+        for line in body {
+            let (t, _) = line.fields(2)?;
+            if t[0].text == "frame" {
+                action.frames.push(FrameData {
+                    msec: t[1].idx()?,
+                    transforms: Vec::new(),
+                });
+                continue;
+            }
+
+            let (t, _) = line.fields(3)?;
+            let numvertex = model.vertices.len();
+            let transform = Transform {
+                boneid: t[0].check(t[0].idx()?, model.bones.len(), "unknown bone")?,
+                pos: t[1].check(t[1].idx()?, numvertex, "unknown vertex")?,
+                ori: t[2].check(t[2].idx()?, numvertex, "unknown vertex")?,
+            };
+            let frame = action
+                .frames
+                .last_mut()
+                .ok_or_else(|| line.err(1, "transform outside of a frame"))?;
+            frame.transforms.push(transform);
+        }
+        model.actions.push(action);
+        Ok(())
+    }
+
+    fn voxel_types(&mut self, body: &[Line]) -> Result<(), SyntaxError> {
+        for line in body {
+            let (t, name) = line.fields(1)?;
+            if t[0].text == "item" {
+                let (t, name) = line.fields(2)?;
+                let count = t[1]
+                    .text
+                    .parse()
+                    .map_err(|_| t[1].err("expected a count"))?;
+                let voxel = self
+                    .text
+                    .model
+                    .voxel_types
+                    .last_mut()
+                    .ok_or_else(|| line.err(1, "item outside of a voxel type"))?;
+                voxel.items.push(VoxelItemData {
+                    count,
+                    name: name.to_string(),
+                });
+                continue;
+            }
+
+            let (t, name) = if name.is_empty() {
+                (t, name)
+            } else {
+                line.fields(4)?
+            };
+            if t.len() < 4 {
+                return Err(line.err(line.text.len() + 1, "missing value"));
+            }
+            let material_id = self
+                .text
+                .model
+                .materials
+                .iter()
+                .position(|m| m.name == name)
+                .map_or(m3dc::M3D_UNDEF, |i| i as _);
+            self.text.model.voxel_types.push(VoxelTypeData {
+                name: name.to_string(),
+                rotation: t[1].byte()?,
+                shape: t[2]
+                    .text
+                    .parse()
+                    .map_err(|_| t[2].err("expected a shape"))?,
+                material_id,
+                color: t[0].color()?,
+                skin_id: t[3].check(t[3].idx()?, self.text.model.skins.len(), "unknown skin")?,
+                items: Vec::new(),
+            });
+        }
+        Ok(())
+    }
+
+    fn voxel_block(&mut self, name: &str, head: Line, body: &[Line]) -> Result<(), SyntaxError> {
+        let mut block = VoxelBlockData {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        for line in body {
+            let tokens = line.tokens();
+            match tokens[0].text {
+                "pos" => {
+                    let (t, _) = line.fields(4)?;
+                    block.x = t[1].signed()?;
+                    block.y = t[2].signed()?;
+                    block.z = t[3].signed()?;
+                }
+                "dim" => {
+                    let (t, _) = line.fields(4)?;
+                    block.width = t[1].idx()?;
+                    block.height = t[2].idx()?;
+                    block.depth = t[3].idx()?;
+                }
+                "uncertain" => block.uncertain = line.fields(2)?.0[1].byte()?,
+                "group" => block.group = line.fields(2)?.0[1].byte()?,
+                _ => {
+                    for token in &tokens {
+                        block.data.push(token.idx()? as u16);
+                    }
+                }
+            }
+        }
+
+        let len = (block.width as usize)
+            .checked_mul(block.height as _)
+            .and_then(|l| l.checked_mul(block.depth as _));
+        if len != Some(block.data.len()) {
+            return Err(head.err(1, "voxel count does not match the dimensions"));
+        }
+        self.text.model.voxels.push(block);
+        Ok(())
+    }
+}
+
+// Map properties are named after their scalar counterpart with a `map_` prefix.
+fn property_by_name(name: &str) -> Option<(u8, u32)> {
+    if let Some(def) = PROPERTY_TYPES.iter().find(|p| p.name == name) {
+        return Some((def.id, def.format));
+    }
+    let base = name.strip_prefix("map_")?;
+    let def = PROPERTY_TYPES[..PROPERTY_TYPES.len() - 3]
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case(base))?;
+    Some((def.id + 128, PF_MAP))
+}
+
+fn property_name(id: u8) -> Option<String> {
+    let base = |id: u8| PROPERTY_TYPES.iter().find(|p| p.id == id).map(|p| p.name);
+    if id >= 128 {
+        base(id - 128).map(|name| format!("map_{}", name))
+    } else {
+        base(id).map(str::to_string)
+    }
+}
+
+// Text which has to stay on one line.
+fn line(s: &str) -> String {
+    s.replace(['\r', '\n'], " ")
+}
+
+// Names used as a single token.
+fn token(s: &str) -> String {
+    match s {
+        "" => "-".to_string(),
+        s => s.replace(char::is_whitespace, "_"),
+    }
+}
+
+fn idx(v: u32) -> i64 {
+    // The two topmost values are written as -1 and -2.
+    if v >= m3dc::M3D_INDEXMAX {
+        v as i32 as i64
+    } else {
+        v as i64
+    }
+}
+
+pub(crate) fn write(data: &ModelData, flags: SaveFlags) -> Result<Vec<u8>, ErrorKind> {
+    let mut out = String::new();
+    write_text(&mut out, data, flags).map_err(|_| ErrorKind::Alloc)?;
+    Ok(out.into_bytes())
+}
+
+fn write_text(out: &mut String, data: &ModelData, flags: SaveFlags) -> std::fmt::Result {
+    let scale = if data.scale > 0.0 { data.scale } else { 1.0 };
+    write!(out, "3dmodel {}\r\n", scale)?;
+    write!(
+        out,
+        "{}\r\n{}\r\n{}\r\n",
+        line(&data.name),
+        line(&data.license),
+        line(&data.author)
+    )?;
+    for desc in data.desc.lines().filter(|l| !l.trim().is_empty()) {
+        write!(out, "{}\r\n", desc.trim())?;
+    }
+    if data.desc.trim().is_empty() {
+        out.push_str("\r\n");
+    }
+    out.push_str("\r\n");
+
+    if !data.texture_maps.is_empty() && !flags.contains(SaveFlags::NO_TEXCOORD) {
+        out.push_str("Textmap\r\n");
+        for uv in &data.texture_maps {
+            let v = if flags.contains(SaveFlags::FLIP_TEXCOORD) {
+                1.0 - uv.v
+            } else {
+                uv.v
+            };
+            write!(out, "{} {}\r\n", uv.u, v)?;
+        }
+        out.push_str("\r\n");
+    }
+
+    // The hierarchy is written depth first, which may reorder the bones.
+    let bones = !flags.contains(SaveFlags::NO_BONE) && !data.bones.is_empty();
+    let mut order = Vec::with_capacity(data.bones.len());
+    let mut levels = vec![0; data.bones.len()];
+    if bones {
+        fn visit(
+            data: &ModelData,
+            parent: u32,
+            level: usize,
+            order: &mut Vec<usize>,
+            levels: &mut [usize],
+        ) {
+            for (i, bone) in data.bones.iter().enumerate() {
+                if bone.parent == parent && !order.contains(&i) {
+                    order.push(i);
+                    levels[i] = level;
+                    visit(data, i as _, level + 1, order, levels);
+                }
+            }
+        }
+        visit(data, m3dc::M3D_UNDEF, 0, &mut order, &mut levels);
+        // Bones with a missing parent become roots.
+        for i in 0..data.bones.len() {
+            if !order.contains(&i) {
+                order.push(i);
+                levels[i] = 0;
+                visit(data, i as _, 1, &mut order, &mut levels);
+            }
+        }
+    }
+    let mut bone_ids = vec![m3dc::M3D_UNDEF; data.bones.len()];
+    for (new, &old) in order.iter().enumerate() {
+        bone_ids[old] = new as u32;
+    }
+    let bone_id = |id: u32| {
+        bone_ids
+            .get(id as usize)
+            .copied()
+            .unwrap_or(m3dc::M3D_UNDEF)
+    };
+
+    if !data.vertices.is_empty() {
+        out.push_str("Vertex\r\n");
+        for v in &data.vertices {
+            write!(out, "{} {} {} {}", v.x, v.y, v.z, v.w)?;
+            if v.color != 0 {
+                write!(out, " #{:08x}", v.color)?;
+            }
+            if let Some(skin) = data.skins.get(v.skinid as usize).filter(|_| bones) {
+                for (&boneid, &weight) in skin.boneid.iter().zip(&skin.weight) {
+                    if boneid != m3dc::M3D_UNDEF && weight > 0.0 {
+                        write!(out, " {}:{}", idx(bone_id(boneid)), weight)?;
+                    }
+                }
+            }
+            out.push_str("\r\n");
+        }
+        out.push_str("\r\n");
+    }
+
+    if bones {
+        out.push_str("Bones\r\n");
+        for &i in &order {
+            let bone = &data.bones[i];
+            let slashes = "/".repeat(levels[i]);
+            write!(
+                out,
+                "{}{} {} {}\r\n",
+                slashes,
+                idx(bone.position),
+                idx(bone.orientation),
+                line(&bone.name)
+            )?;
+        }
+        out.push_str("\r\n");
+    }
+
+    let materials = !flags.contains(SaveFlags::NO_MATERIAL);
+    if materials {
+        for material in &data.materials {
+            write!(out, "Material {}\r\n", token(&material.name))?;
+            for prop in &material.props {
+                let name = match property_name(prop.type_) {
+                    Some(name) => name,
+                    None => continue,
+                };
+                match property_format(prop.type_) {
+                    Some(PF_COLOR) => {
+                        write!(out, "{} #{:08x}\r\n", name, unsafe { prop.value.color })?
+                    }
+                    Some(PF_FLOAT) => write!(out, "{} {}\r\n", name, unsafe { prop.value.fnum })?,
+                    Some(PF_MAP) => {
+                        if let Some(texture) =
+                            data.textures.get(unsafe { prop.value.textureid } as usize)
+                        {
+                            write!(out, "{} {}\r\n", name, line(&texture.name))?;
+                        }
+                    }
+                    _ => write!(out, "{} {}\r\n", name, unsafe { prop.value.num })?,
+                }
+            }
+            out.push_str("\r\n");
+        }
+    }
+    let material_name = |id: u32| match data.materials.get(id as usize) {
+        Some(material) if materials => token(&material.name),
+        _ => "-".to_string(),
+    };
+
+    if !data.faces.is_empty() && !flags.contains(SaveFlags::NO_FACE) {
+        out.push_str("Mesh\r\n");
+        let normals = !flags.contains(SaveFlags::NO_NORMAL) && !data.generated_normals;
+        let texcoords = !flags.contains(SaveFlags::NO_TEXCOORD);
+        let mut materialid = m3dc::M3D_UNDEF;
+        for face in &data.faces {
+            let mut face = *face;
+            if flags.contains(SaveFlags::IDOSUCK) {
+                face.vertex.swap(1, 2);
+                face.normal.swap(1, 2);
+                face.texcoord.swap(1, 2);
+            }
+            let id = if materials {
+                face.materialid
+            } else {
+                m3dc::M3D_UNDEF
+            };
+            if id != materialid {
+                write!(out, "use {}\r\n", material_name(id))?;
+                materialid = id;
+            }
+
+            for j in 0..3 {
+                let sep = if j == 0 { "" } else { " " };
+                write!(out, "{}{}", sep, idx(face.vertex[j]))?;
+                let texcoord = texcoords && face.texcoord[j] != m3dc::M3D_UNDEF;
+                let normal = normals && face.normal[j] != m3dc::M3D_UNDEF;
+                if texcoord {
+                    write!(out, "/{}", face.texcoord[j])?;
+                }
+                if normal {
+                    let sep = if texcoord { "/" } else { "//" };
+                    write!(out, "{}{}", sep, face.normal[j])?;
+                }
+            }
+            out.push_str("\r\n");
+        }
+        out.push_str("\r\n");
+    }
+
+    for shape in &data.shapes {
+        write!(out, "Shape {}\r\n", line(&shape.name))?;
+        if bones && bone_id(shape.group) != m3dc::M3D_UNDEF {
+            write!(out, "group {}\r\n", bone_id(shape.group))?;
+        }
+        for cmd in &shape.commands {
+            let (def, types) = match (
+                COMMAND_TYPES.get(cmd.kind as usize),
+                command_arg_types(cmd.kind, &cmd.args),
+            ) {
+                (Some(def), Some(types)) => (def, types),
+                _ => continue,
+            };
+            out.push_str(def.name);
+            for (&ty, &arg) in types.iter().zip(&cmd.args) {
+                match ty {
+                    MI => write!(out, " {}", material_name(arg))?,
+                    VC => write!(out, " {}", f32::from_bits(arg))?,
+                    _ => write!(out, " {}", idx(arg))?,
+                }
+            }
+            out.push_str("\r\n");
+        }
+        out.push_str("\r\n");
+    }
+
+    let mut labels = &data.labels[..];
+    while let Some(first) = labels.first() {
+        let len = labels
+            .iter()
+            .take_while(|l| l.name == first.name && l.lang == first.lang && l.color == first.color)
+            .count();
+        write!(out, "Labels {}\r\n", line(&first.name))?;
+        if !first.lang.is_empty() {
+            write!(out, "lang {}\r\n", line(&first.lang))?;
+        }
+        if first.color != 0 {
+            write!(out, "color #{:08x}\r\n", first.color)?;
+        }
+        for label in &labels[..len] {
+            write!(out, "{} {}\r\n", idx(label.vertex_id), line(&label.text))?;
+        }
+        out.push_str("\r\n");
+        labels = &labels[len..];
+    }
+
+    if bones && !flags.contains(SaveFlags::NO_ACTION) {
+        for action in &data.actions {
+            write!(
+                out,
+                "Action {} {}\r\n",
+                action.duration_msec,
+                line(&action.name)
+            )?;
+            for frame in &action.frames {
+                write!(out, "frame {}\r\n", frame.msec)?;
+                for t in &frame.transforms {
+                    write!(
+                        out,
+                        "{} {} {}\r\n",
+                        idx(bone_id(t.boneid)),
+                        idx(t.pos),
+                        idx(t.ori)
+                    )?;
+                }
+            }
+            out.push_str("\r\n");
+        }
+    }
+
+    if !data.voxel_types.is_empty() {
+        out.push_str("VoxTypes\r\n");
+        for voxel in &data.voxel_types {
+            let skin = if bones {
+                voxel.skin_id
+            } else {
+                m3dc::M3D_UNDEF
+            };
+            write!(
+                out,
+                "#{:08x} {} {} {} {}\r\n",
+                voxel.color,
+                voxel.rotation,
+                voxel.shape,
+                idx(skin),
+                line(&voxel.name)
+            )?;
+            for item in &voxel.items {
+                write!(out, "item {} {}\r\n", item.count, line(&item.name))?;
+            }
+        }
+        out.push_str("\r\n");
+    }
+
+    for block in &data.voxels {
+        write!(out, "Voxel {}\r\n", line(&block.name))?;
+        write!(out, "pos {} {} {}\r\n", block.x, block.y, block.z)?;
+        write!(
+            out,
+            "dim {} {} {}\r\n",
+            block.width, block.height, block.depth
+        )?;
+        write!(
+            out,
+            "uncertain {}\r\ngroup {}\r\n",
+            block.uncertain, block.group
+        )?;
+        for row in block.data.chunks(block.width.max(1) as usize) {
+            let row: Vec<String> = row.iter().map(|&v| (v as i16).to_string()).collect();
+            write!(out, "{}\r\n", row.join(" "))?;
+        }
+        out.push_str("\r\n");
+    }
+    Ok(())
+}
@@ -0,0 +1,1065 @@
+use super::*;
+use crate::loader::LoadContext;
+use std::borrow::Cow;
+
+#[derive(Clone)]
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], ErrorKind> {
+        if len > self.data.len() {
+            return Err(ErrorKind::Truncating);
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ErrorKind> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, ErrorKind> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ErrorKind> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, ErrorKind> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, ErrorKind> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, ErrorKind> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    // Like `_m3d_getidx`, the two topmost values of narrow indices are negative.
+    fn idx(&mut self, size: u8) -> Result<Option<u32>, ErrorKind> {
+        Ok(match size {
+            0 => None,
+            1 => {
+                let v = self.u8()?;
+                Some(if v > 253 { v as i8 as u32 } else { v as u32 })
+            }
+            2 => {
+                let v = self.u16()?;
+                Some(if v > 65533 { v as i16 as u32 } else { v as u32 })
+            }
+            4 => Some(self.u32()?),
+            _ => return Err(ErrorKind::BadFile),
+        })
+    }
+
+    fn idx_or(&mut self, size: u8, default: u32) -> Result<u32, ErrorKind> {
+        Ok(self.idx(size)?.unwrap_or(default))
+    }
+
+    fn coord(&mut self, size: u8) -> Result<f32, ErrorKind> {
+        Ok(match size {
+            1 => self.u8()? as i8 as f32 / 127.0,
+            2 => self.u16()? as i16 as f32 / 32767.0,
+            4 => self.f32()?,
+            8 => self.f64()? as f32,
+            _ => return Err(ErrorKind::BadFile),
+        })
+    }
+
+    fn unsigned_coord(&mut self, size: u8) -> Result<f32, ErrorKind> {
+        Ok(match size {
+            1 => self.u8()? as f32 / 255.0,
+            2 => self.u16()? as f32 / 65535.0,
+            4 => self.f32()?,
+            8 => self.f64()? as f32,
+            _ => return Err(ErrorKind::BadFile),
+        })
+    }
+
+    fn signed(&mut self, size: u8) -> Result<i32, ErrorKind> {
+        Ok(match size {
+            1 => self.u8()? as i8 as i32,
+            2 => self.u16()? as i16 as i32,
+            4 => self.u32()? as i32,
+            _ => return Err(ErrorKind::BadFile),
+        })
+    }
+
+    fn unsigned(&mut self, size: u8) -> Result<u32, ErrorKind> {
+        Ok(match size {
+            1 => self.u8()? as u32,
+            2 => self.u16()? as u32,
+            4 => self.u32()?,
+            _ => return Err(ErrorKind::BadFile),
+        })
+    }
+
+    fn color(&mut self, size: u8, cmap: &[u32]) -> Result<u32, ErrorKind> {
+        Ok(match size {
+            0 => 0,
+            1 => cmap.get(self.u8()? as usize).copied().unwrap_or(0),
+            2 => cmap.get(self.u16()? as usize).copied().unwrap_or(0),
+            4 => self.u32()?,
+            _ => return Err(ErrorKind::BadFile),
+        })
+    }
+}
+
+struct Chunk<'a> {
+    magic: [u8; 4],
+    raw: &'a [u8],
+    payload: &'a [u8],
+}
+
+struct Decoder<'a, 'c> {
+    sizes: Sizes,
+    strings: &'a [u8],
+    ctx: &'c LoadContext<'c>,
+    model: ModelData,
+    warnings: Vec<Error>,
+}
+
+pub(crate) fn decode<'c>(
+    data: &[u8],
+    ctx: &'c LoadContext<'c>,
+    mtllib: Option<&Obj>,
+) -> Result<(ModelData, Vec<Error>), Error> {
+    if data.starts_with(b"3dmodel") {
+        return decode_text(data, ctx, mtllib);
+    }
+    if data.len() < 8 || !data.starts_with(b"3DMO") {
+        return Err(ErrorKind::BadFile.into());
+    }
+
+    let len = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+    let mut body = data.get(8..len.min(data.len())).ok_or(ErrorKind::BadFile)?;
+
+    let mut preview = Vec::new();
+    if body.starts_with(b"PRVW") {
+        let mut r = Reader::new(&body[4..]);
+        let len = r.u32()? as usize;
+        if len < 8 || len > body.len() {
+            return Err(ErrorKind::BadFile.into());
+        }
+        preview = body[8..len].to_vec();
+        body = &body[len..];
+    }
+
+    let raw: Cow<[u8]> = if body.starts_with(b"HEAD") {
+        Cow::Borrowed(body)
+    } else {
+        Cow::Owned(
+            miniz_oxide::inflate::decompress_to_vec_zlib(body).map_err(|_| ErrorKind::BadFile)?,
+        )
+    };
+
+    let mut r = Reader::new(&raw);
+    if r.bytes(4)? != b"HEAD" {
+        return Err(ErrorKind::BadFile.into());
+    }
+    let head_len = r.u32()? as usize;
+    if head_len < 16 || head_len > raw.len() {
+        return Err(ErrorKind::BadFile.into());
+    }
+    let scale = r.f32()?;
+    let sizes = Sizes::from_types(r.u32()?);
+    if sizes.vi > 4 || sizes.si > 4 || sizes.vd > 4 || sizes.vp > 2 {
+        return Err(ErrorKind::BadFile.into());
+    }
+
+    let mut decoder = Decoder {
+        sizes,
+        strings: &raw[16..head_len],
+        ctx,
+        model: ModelData {
+            scale,
+            preview,
+            ..Default::default()
+        },
+        warnings: Vec::new(),
+    };
+    if sizes.vc > 4 || sizes.nb as u32 > m3dc::M3D_NUMBONE {
+        decoder.warnings.push(ErrorKind::Truncating.into());
+    }
+
+    let mut header = decoder.strings.split(|&c| c == 0);
+    let mut next_str = || String::from_utf8_lossy(header.next().unwrap_or_default()).into_owned();
+    decoder.model.name = next_str();
+    decoder.model.license = next_str();
+    decoder.model.author = next_str();
+    decoder.model.desc = next_str();
+
+    if let Some(mtllib) = mtllib {
+        decoder.seed_materials(mtllib);
+    }
+
+    let chunks = decoder.chunks(&raw[head_len..]);
+    decoder.collect_assets(&chunks);
+    for chunk in &chunks {
+        let result = decoder.chunk(chunk);
+        if let Err(err) = result {
+            let magic = String::from_utf8_lossy(&chunk.magic);
+            decoder.warnings.push(Error::from(err).with_chunk(&magic));
+        }
+    }
+    decoder.finish();
+
+    Ok((decoder.model, decoder.warnings))
+}
+
+fn decode_text<'c>(
+    data: &[u8],
+    ctx: &'c LoadContext<'c>,
+    mtllib: Option<&Obj>,
+) -> Result<(ModelData, Vec<Error>), Error> {
+    let text = std::str::from_utf8(data).map_err(|_| ErrorKind::BadFile)?;
+    let mut decoder = Decoder {
+        sizes: Sizes::from_types(0),
+        strings: &[],
+        ctx,
+        model: ModelData::default(),
+        warnings: Vec::new(),
+    };
+    if let Some(mtllib) = mtllib {
+        decoder.seed_materials(mtllib);
+    }
+    let (seeded_textures, seeded_materials) =
+        (decoder.model.textures.len(), decoder.model.materials.len());
+
+    let parsed = ascii::parse(text, std::mem::take(&mut decoder.model))?;
+    decoder.model = parsed.model;
+    decoder.warnings.extend(parsed.warnings);
+
+    // The parser only knows texture names, load them and drop maps which failed to.
+    let names: Vec<String> = decoder
+        .model
+        .textures
+        .drain(seeded_textures..)
+        .map(|t| t.name)
+        .collect();
+    let ids: Vec<u32> = names.iter().map(|name| decoder.texture(name)).collect();
+    for material in &mut decoder.model.materials[seeded_materials..] {
+        material.props.retain_mut(|prop| {
+            if property_format(prop.type_) != Some(PF_MAP) {
+                return true;
+            }
+            let id = unsafe { prop.value.textureid } as usize;
+            match id.checked_sub(seeded_textures).map(|i| ids[i]) {
+                Some(m3dc::M3D_UNDEF) => false,
+                Some(id) => {
+                    prop.value.textureid = id;
+                    true
+                }
+                None => true,
+            }
+        });
+    }
+
+    if let Some(preview) = parsed.preview.and_then(|name| decoder.asset(&name)) {
+        decoder.model.preview = preview;
+    }
+    for name in &parsed.procedurals {
+        if let Err(err) = decoder.run_procedural(name) {
+            decoder.warnings.push(Error::from(err).with_asset(name));
+        }
+    }
+    decoder.finish();
+
+    Ok((decoder.model, decoder.warnings))
+}
+
+impl<'a, 'c> Decoder<'a, 'c> {
+    fn string(&self, offset: u32) -> Result<Option<String>, ErrorKind> {
+        if offset == 0 {
+            return Ok(None);
+        }
+        let tail = self
+            .strings
+            .get(offset as usize..)
+            .ok_or(ErrorKind::BadFile)?;
+        let end = tail
+            .iter()
+            .position(|&c| c == 0)
+            .ok_or(ErrorKind::BadFile)?;
+        Ok(Some(String::from_utf8_lossy(&tail[..end]).into_owned()))
+    }
+
+    fn str(&self, r: &mut Reader) -> Result<Option<String>, ErrorKind> {
+        let offset = r.idx_or(self.sizes.si, 0)?;
+        self.string(offset)
+    }
+
+    fn material_id(&self, name: &str) -> Option<u32> {
+        self.model
+            .materials
+            .iter()
+            .position(|m| m.name == name)
+            .map(|i| i as _)
+    }
+
+    fn check_cmap(&mut self) {
+        if self.sizes.ci > 0 && self.sizes.ci < 4 && self.model.color_maps.is_empty() {
+            self.warnings.push(ErrorKind::ColorMap.into());
+        }
+    }
+
+    fn seed_materials(&mut self, mtllib: &Obj) {
+        self.model.textures = mtllib
+            .textures()
+            .iter()
+            .map(|t| TextureData {
+                name: t.name().to_string(),
+                width: t.width(),
+                height: t.height(),
+                format: t.format(),
+                pixels: t.data().to_vec(),
+            })
+            .collect();
+        self.model.materials = mtllib
+            .materials()
+            .iter()
+            .map(|m| MaterialData {
+                name: m.name().to_string(),
+                props: m.props().to_vec(),
+            })
+            .collect();
+    }
+
+    fn chunks(&mut self, mut data: &'a [u8]) -> Vec<Chunk<'a>> {
+        let mut chunks = Vec::new();
+        while data.len() >= 8 && !data.starts_with(b"OMD3") {
+            let magic: [u8; 4] = data[0..4].try_into().unwrap();
+            let len = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+            if len < 8 || len > data.len() {
+                self.warnings.push(ErrorKind::BadFile.into());
+                break;
+            }
+            chunks.push(Chunk {
+                magic,
+                raw: &data[..len],
+                payload: &data[8..len],
+            });
+            data = &data[len..];
+        }
+        chunks
+    }
+
+    // Inlined assets come first, materials may reference them regardless of chunk order.
+    fn collect_assets(&mut self, chunks: &[Chunk]) {
+        for chunk in chunks.iter().filter(|c| &c.magic == b"ASET") {
+            let mut r = Reader::new(chunk.payload);
+            match self.str(&mut r) {
+                Ok(name) => self.model.inlined.push(InlinedData {
+                    name: name.unwrap_or_default(),
+                    data: r.data.to_vec(),
+                }),
+                Err(err) => self.warnings.push(Error::from(err).with_chunk("ASET")),
+            }
+        }
+    }
+
+    fn chunk(&mut self, chunk: &Chunk) -> Result<(), ErrorKind> {
+        let r = &mut Reader::new(chunk.payload);
+        match &chunk.magic {
+            b"CMAP" => self.cmap(r),
+            b"TMAP" => self.tmap(r),
+            b"VRTS" => self.vertices(r),
+            b"BONE" => self.bones(r),
+            b"MTRL" => self.material(r),
+            b"PROC" => self.procedural(r),
+            b"MESH" => self.mesh(r),
+            b"VOXT" => self.voxel_types(r),
+            b"VOXD" => self.voxel_block(r),
+            b"SHPE" => self.shape(r),
+            b"LBLS" => self.labels(r),
+            b"ACTN" => self.action(r),
+            b"ASET" | b"PRVW" => Ok(()),
+            _ => {
+                self.model.extra.push(chunk.raw.to_vec());
+                Ok(())
+            }
+        }
+    }
+
+    fn cmap(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        if !self.model.color_maps.is_empty() {
+            return Err(ErrorKind::ColorMap);
+        }
+        while r.len() >= 4 {
+            self.model.color_maps.push(r.u32()?);
+        }
+        Ok(())
+    }
+
+    fn tmap(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        if !self.model.texture_maps.is_empty() {
+            return Err(ErrorKind::TextureMap);
+        }
+        let reclen = self.sizes.vc as usize * 2;
+        self.model.texture_maps.reserve(r.len() / reclen);
+        while r.len() >= reclen {
+            let u = r.unsigned_coord(self.sizes.vc)?;
+            let v = r.unsigned_coord(self.sizes.vc)?;
+            self.model.texture_maps.push(TextureMapIndex { u, v });
+        }
+        Ok(())
+    }
+
+    fn vertices(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        if !self.model.vertices.is_empty() {
+            return Err(ErrorKind::Vertices);
+        }
+        self.check_cmap();
+
+        let s = self.sizes;
+        let reclen = s.ci as usize + s.sk as usize + 4 * s.vc as usize;
+        self.model.vertices.reserve(r.len() / reclen);
+        while r.len() >= reclen {
+            let vertex = Vertex {
+                x: r.coord(s.vc)?,
+                y: r.coord(s.vc)?,
+                z: r.coord(s.vc)?,
+                w: r.coord(s.vc)?,
+                color: r.color(s.ci, &self.model.color_maps)?,
+                skinid: r.idx_or(s.sk, m3dc::M3D_UNDEF)?,
+            };
+            self.model.vertices.push(vertex);
+        }
+        Ok(())
+    }
+
+    fn bones(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        if !self.model.bones.is_empty() {
+            return Err(ErrorKind::Bone);
+        }
+
+        let s = self.sizes;
+        let numbone = r.idx_or(s.bi, 0)? as usize;
+        let numskin = r.idx_or(s.sk, 0)? as usize;
+
+        self.model.bones.reserve(numbone.min(r.len()));
+        for _ in 0..numbone {
+            let bone = (|| {
+                Ok::<_, ErrorKind>(BoneData {
+                    parent: r.idx_or(s.bi, m3dc::M3D_UNDEF)?,
+                    name: self.str(r)?.unwrap_or_default(),
+                    position: r.idx_or(s.vi, m3dc::M3D_UNDEF)?,
+                    orientation: r.idx_or(s.vi, m3dc::M3D_UNDEF)?,
+                    weights: Vec::new(),
+                    mat4: [0.0; 16],
+                })
+            })();
+            match bone {
+                Ok(bone) => self.model.bones.push(bone),
+                Err(_) => return Err(ErrorKind::Bone),
+            }
+        }
+
+        self.model.skins.reserve(numskin.min(r.len()));
+        for _ in 0..numskin {
+            // With a single bone per vertex and no bone indices, a skin takes no bytes at all, so
+            // the count alone is not bounded by the chunk.
+            if r.is_empty() {
+                self.warnings
+                    .push(Error::from(ErrorKind::Truncating).with_chunk("BONE"));
+                break;
+            }
+            let mut skin = Skin {
+                boneid: [m3dc::M3D_UNDEF; m3dc::M3D_NUMBONE as usize],
+                weight: [0.0; m3dc::M3D_NUMBONE as usize],
+            };
+
+            let mut weights = [0u8; 8];
+            if s.nb == 1 {
+                weights[0] = 255;
+            } else {
+                weights[..s.nb as usize]
+                    .copy_from_slice(r.bytes(s.nb as _).map_err(|_| ErrorKind::Bone)?);
+            }
+
+            let mut total = 0.0;
+            for (j, &weight) in weights[..s.nb as usize].iter().enumerate() {
+                if weight == 0 {
+                    continue;
+                }
+                let boneid = r
+                    .idx_or(s.bi, m3dc::M3D_UNDEF)
+                    .map_err(|_| ErrorKind::Bone)?;
+                // Models with more bones per vertex than supported lose the extra weights.
+                if j < m3dc::M3D_NUMBONE as usize {
+                    skin.boneid[j] = boneid;
+                    skin.weight[j] = weight as f32 / 255.0;
+                    total += skin.weight[j];
+                }
+            }
+            if total != 1.0 && total != 0.0 {
+                skin.weight.iter_mut().for_each(|w| *w /= total);
+            }
+
+            self.model.skins.push(skin);
+        }
+        Ok(())
+    }
+
+    fn material(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        let name = self.str(r)?.unwrap_or_default();
+        if self.material_id(&name).is_some() {
+            return Err(ErrorKind::Material);
+        }
+
+        let mut material = MaterialData {
+            name,
+            props: Vec::new(),
+        };
+        while !r.is_empty() {
+            let type_ = r.u8()?;
+            let format = match property_format(type_) {
+                Some(format) => format,
+                None => {
+                    self.warnings.push(ErrorKind::UnkownProperty.into());
+                    break;
+                }
+            };
+
+            let mut prop = MaterialProp {
+                type_,
+                value: unsafe { std::mem::zeroed() },
+            };
+            match format {
+                PF_COLOR => prop.value.color = r.color(self.sizes.ci, &self.model.color_maps)?,
+                PF_UINT8 => prop.value.num = r.u8()? as _,
+                PF_UINT16 => prop.value.num = r.u16()? as _,
+                PF_UINT32 => prop.value.num = r.u32()?,
+                PF_FLOAT => prop.value.fnum = r.f32()?,
+                _ => {
+                    let name = self.str(r)?.unwrap_or_default();
+                    prop.value.textureid = self.texture(&name);
+                    // Missing textures drop the property, like the C SDK does.
+                    if unsafe { prop.value.textureid } == m3dc::M3D_UNDEF {
+                        continue;
+                    }
+                }
+            }
+            material.props.push(prop);
+        }
+
+        material.props.truncate(u8::MAX as _);
+        self.model.materials.push(material);
+        Ok(())
+    }
+
+    fn asset(&self, name: &str) -> Option<Vec<u8>> {
+        self.model
+            .inlined
+            .iter()
+            .find(|i| i.name == name)
+            .map(|i| i.data.clone())
+            .or_else(|| self.ctx.resolve(name))
+    }
+
+    fn texture(&mut self, name: &str) -> u32 {
+        if let Some(id) = self.model.textures.iter().position(|t| t.name == name) {
+            return id as _;
+        }
+
+        // Names without an extension are tried as PNG first.
+        let bytes = self
+            .model
+            .inlined
+            .iter()
+            .find(|i| i.name == name)
+            .map(|i| i.data.clone())
+            .or_else(|| {
+                let has_ext = name.len() >= 5 && name.as_bytes()[name.len() - 4] == b'.';
+                (!has_ext)
+                    .then(|| self.ctx.resolve(&format!("{}.png", name)))
+                    .flatten()
+            })
+            .or_else(|| self.ctx.resolve(name));
+        let bytes = match bytes {
+            Some(bytes) => bytes,
+            None => {
+                // Missing textures are not cached, warn about each only once.
+                let warned = self
+                    .warnings
+                    .iter()
+                    .any(|w| w.kind() == ErrorKind::UnknownImg && w.asset() == Some(name));
+                if !warned {
+                    self.warnings
+                        .push(Error::from(ErrorKind::UnknownImg).with_asset(name));
+                }
+                return m3dc::M3D_UNDEF;
+            }
+        };
+
+        let mut texture = TextureData {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let generated = if bytes.starts_with(b"\x89PNG") {
+            decode_png(&bytes)
+        } else {
+            self.ctx
+                .texture_script
+                .and_then(|handler| handler.generate(name, &bytes))
+                .filter(GeneratedTexture::is_valid)
+                .filter(|t| self.ctx.allow_texture(name, t.width, t.height))
+        };
+        match generated {
+            Some(generated) => {
+                texture.width = generated.width;
+                texture.height = generated.height;
+                texture.format = generated.format;
+                texture.pixels = generated.pixels;
+            }
+            None => self
+                .warnings
+                .push(Error::from(ErrorKind::UnknownImg).with_asset(name)),
+        }
+
+        self.model.textures.push(texture);
+        (self.model.textures.len() - 1) as _
+    }
+
+    fn procedural(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        match self.str(r)? {
+            Some(name) => self.run_procedural(&name),
+            None => Ok(()),
+        }
+    }
+
+    fn run_procedural(&mut self, name: &str) -> Result<(), ErrorKind> {
+        let script = self.asset(name).ok_or(ErrorKind::UnknownImg)?;
+        // Reported like the SDK hook does.
+        let handler = self.ctx.procedural.ok_or(ErrorKind::Unimplemented)?;
+        handler
+            .generate(
+                name,
+                &script,
+                &mut ProceduralModel::from_data(&mut self.model),
+            )
+            .map_err(|err| err.kind())
+    }
+
+    fn mesh(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        let s = self.sizes;
+        let mut materialid = m3dc::M3D_UNDEF;
+        while !r.is_empty() {
+            let k = r.u8()?;
+            let n = k >> 4;
+            let k = k & 15;
+
+            if n == 0 {
+                materialid = m3dc::M3D_UNDEF;
+                if let Some(name) = self.str(r)? {
+                    match self.material_id(&name) {
+                        Some(id) => materialid = id,
+                        None => self.warnings.push(ErrorKind::Material.into()),
+                    }
+                }
+                continue;
+            }
+            if n != 3 {
+                return Err(ErrorKind::UnknownMesh);
+            }
+
+            let mut face = Face {
+                materialid,
+                vertex: [m3dc::M3D_UNDEF; 3],
+                normal: [m3dc::M3D_UNDEF; 3],
+                texcoord: [m3dc::M3D_UNDEF; 3],
+            };
+            let read = |r: &mut Reader, face: &mut Face, j: usize| -> Result<(), ErrorKind> {
+                face.vertex[j] = r.idx_or(s.vi, m3dc::M3D_UNDEF)?;
+                if k & 1 != 0 {
+                    face.texcoord[j] = r.idx_or(s.ti, m3dc::M3D_UNDEF)?;
+                }
+                if k & 2 != 0 {
+                    face.normal[j] = r.idx_or(s.vi, m3dc::M3D_UNDEF)?;
+                }
+                // Maximum vertex, not supported by this SDK version.
+                if k & 4 != 0 {
+                    r.idx(s.vi)?;
+                }
+                Ok(())
+            };
+            for j in 0..3 {
+                read(r, &mut face, j).map_err(|_| ErrorKind::UnknownMesh)?;
+            }
+            self.model.faces.push(face);
+        }
+        Ok(())
+    }
+
+    fn voxel_types(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        if !self.model.voxel_types.is_empty() {
+            return Err(ErrorKind::VoxelType);
+        }
+        self.check_cmap();
+
+        let s = self.sizes;
+        while !r.is_empty() {
+            let color = r.color(s.ci, &self.model.color_maps)?;
+            let name = self.str(r)?.unwrap_or_default();
+            let material_id = self.material_id(&name).unwrap_or(m3dc::M3D_UNDEF);
+            let flags = r.u8()?;
+            let shape = (((flags & 0x40) as u16) << 2) | r.u8()? as u16;
+            let numitem = r.u8()?;
+            let skin_id = r.idx_or(s.sk, m3dc::M3D_UNDEF)?;
+
+            let mut items = Vec::with_capacity(numitem as _);
+            for _ in 0..numitem {
+                let count = r.u16()?;
+                let name = self.str(r)?.unwrap_or_default();
+                items.push(VoxelItemData { count, name });
+            }
+
+            self.model.voxel_types.push(VoxelTypeData {
+                name,
+                rotation: flags & 0xBF,
+                shape,
+                material_id,
+                color,
+                skin_id,
+                items,
+            });
+        }
+        Ok(())
+    }
+
+    fn voxel_block(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        let s = self.sizes;
+        let name = self.str(r)?.unwrap_or_default();
+        let mut block = VoxelBlockData {
+            name,
+            x: r.signed(s.vd)?,
+            y: r.signed(s.vd)?,
+            z: r.signed(s.vd)?,
+            width: r.unsigned(s.vd)?,
+            height: r.unsigned(s.vd)?,
+            depth: r.unsigned(s.vd)?,
+            uncertain: r.u8()?,
+            group: r.u8()?,
+            data: Vec::new(),
+        };
+
+        // A run encodes at most 128 voxels per byte, refuse sizes the chunk can't back.
+        let len = (block.width as usize)
+            .checked_mul(block.height as _)
+            .and_then(|l| l.checked_mul(block.depth as _))
+            .filter(|&l| l / 128 <= r.len())
+            .ok_or(ErrorKind::UnknownkVoxex)?;
+
+        block.data = vec![m3dc::M3D_VOXUNDEF as u16; len];
+        let mut j = 0;
+        while j < len && !r.is_empty() {
+            let run = r.u8()?;
+            let count = (run & 0x7F) as usize + 1;
+            if run & 0x80 != 0 {
+                let voxel = r.idx_or(s.vp, m3dc::M3D_VOXUNDEF)? as u16;
+                for v in block.data[j..(j + count).min(len)].iter_mut() {
+                    *v = voxel;
+                }
+                j += count;
+            } else {
+                for _ in 0..count {
+                    let voxel = r.idx_or(s.vp, m3dc::M3D_VOXUNDEF)? as u16;
+                    if j < len {
+                        block.data[j] = voxel;
+                    }
+                    j += 1;
+                }
+            }
+        }
+
+        self.model.voxels.push(block);
+        Ok(())
+    }
+
+    fn shape(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        let s = self.sizes;
+        let mut shape = ShapeData {
+            name: self.str(r)?.unwrap_or_default(),
+            group: r.idx_or(s.bi, m3dc::M3D_UNDEF)?,
+            commands: Vec::new(),
+        };
+        if shape.group != m3dc::M3D_UNDEF && shape.group as usize >= self.model.bones.len() {
+            shape.group = m3dc::M3D_UNDEF;
+            self.warnings.push(ErrorKind::Shape.into());
+        }
+
+        while !r.is_empty() {
+            let mut kind = r.u8()? as u16;
+            if kind & 0x80 != 0 {
+                kind = (kind & 0x7F) | ((r.u8()? as u16) << 7);
+            }
+            let def = match COMMAND_TYPES.get(kind as usize) {
+                Some(def) => def,
+                None => {
+                    self.warnings.push(ErrorKind::UnknownCmd.into());
+                    break;
+                }
+            };
+
+            let p = def.args.len();
+            let mut args = vec![0u32; p];
+            let (mut k, mut n, mut l) = (0, 0, p);
+            while k < l {
+                let arg = match def.args[((k - n) % (p - n)) + n] {
+                    MI => match self.str(r)? {
+                        Some(name) => self.material_id(&name).unwrap_or_else(|| {
+                            self.warnings.push(ErrorKind::Material.into());
+                            m3dc::M3D_NOTDEFINED
+                        }),
+                        None => m3dc::M3D_NOTDEFINED,
+                    },
+                    VC => r.coord(s.vc)?.to_bits(),
+                    HI => r.idx_or(s.hi, 0)?,
+                    FI => r.idx_or(s.fi, 0)?,
+                    TI => r.idx_or(s.ti, 0)?,
+                    VI | QI => r.idx_or(s.vi, 0)?,
+                    I1 => r.idx_or(1, 0)?,
+                    I2 => r.idx_or(2, 0)?,
+                    VA => {
+                        // Variadic, the remaining arguments repeat `count` times.
+                        let count = r.idx_or(4, 0)? as usize;
+                        if count == 0 || count > r.len() {
+                            return Err(ErrorKind::Shape);
+                        }
+                        n = k + 1;
+                        l += (count - 1) * (p - k - 1);
+                        args.resize(l, 0);
+                        count as u32
+                    }
+                    _ => r.idx_or(4, 0)?,
+                };
+                args[k] = arg;
+                k += 1;
+            }
+
+            shape.commands.push(ShapeCommandData { kind, args });
+        }
+
+        self.model.shapes.push(shape);
+        Ok(())
+    }
+
+    fn labels(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        let s = self.sizes;
+        let name = self.str(r)?.unwrap_or_default();
+        let lang = self.str(r)?.unwrap_or_default();
+        self.check_cmap();
+        let color = r.color(s.ci, &self.model.color_maps)?;
+
+        let reclen = s.vi as usize + s.si as usize;
+        while r.len() >= reclen {
+            let vertex_id = r.idx_or(s.vi, m3dc::M3D_UNDEF)?;
+            let text = self.str(r)?.unwrap_or_default();
+            self.model.labels.push(LabelData {
+                name: name.clone(),
+                lang: lang.clone(),
+                text,
+                color,
+                vertex_id,
+            });
+        }
+        Ok(())
+    }
+
+    fn action(&mut self, r: &mut Reader) -> Result<(), ErrorKind> {
+        let s = self.sizes;
+        let name = self.str(r)?.unwrap_or_default();
+        let numframe = r.u16()?;
+        if numframe == 0 {
+            return Ok(());
+        }
+
+        let mut action = ActionData {
+            name,
+            duration_msec: r.u32()?,
+            frames: Vec::with_capacity(numframe as _),
+        };
+        let frames = (|| {
+            for _ in 0..numframe {
+                let msec = r.u32()?;
+                let numtransform = r.idx_or(s.fc, 0)? as usize;
+                let mut transforms = Vec::with_capacity(numtransform.min(r.len()));
+                for _ in 0..numtransform {
+                    transforms.push(Transform {
+                        boneid: r.idx_or(s.bi, m3dc::M3D_UNDEF)?,
+                        pos: r.idx_or(s.vi, m3dc::M3D_UNDEF)?,
+                        ori: r.idx_or(s.vi, m3dc::M3D_UNDEF)?,
+                    });
+                }
+                action.frames.push(FrameData { msec, transforms });
+            }
+            Ok::<_, ErrorKind>(())
+        })();
+
+        self.model.actions.push(action);
+        frames.map_err(|_| ErrorKind::UnknownFrame)
+    }
+
+    fn finish(&mut self) {
+        if self.ctx.generate_normals {
+            self.generate_normals();
+        }
+        self.bone_matrices();
+        self.bone_weights();
+    }
+
+    // Same fast path as the C SDK: one averaged normal per vertex, appended after the vertices.
+    fn generate_normals(&mut self) {
+        let model = &mut self.model;
+        let n = model.vertices.len();
+        let missing =
+            |f: &Face| f.normal[0] == m3dc::M3D_UNDEF && f.vertex.iter().all(|&v| (v as usize) < n);
+        if n == 0 || !model.faces.iter().any(missing) {
+            return;
+        }
+
+        let mut normals = vec![[0f32; 3]; n];
+        for face in model.faces.iter_mut().filter(|f| missing(f)) {
+            let [v0, v1, v2] = face.vertex.map(|v| model.vertices[v as usize]);
+            let a = [v1.x - v0.x, v1.y - v0.y, v1.z - v0.z];
+            let b = [v2.x - v0.x, v2.y - v0.y, v2.z - v0.z];
+            let normal = normalize([
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]);
+            for j in 0..3 {
+                let acc = &mut normals[face.vertex[j] as usize];
+                acc.iter_mut().zip(normal).for_each(|(a, n)| *a += n);
+                face.normal[j] = face.vertex[j] + n as u32;
+            }
+        }
+
+        model.vertices.extend(normals.into_iter().map(|normal| {
+            let [x, y, z] = normalize(normal);
+            Vertex {
+                x,
+                y,
+                z,
+                w: 0.0,
+                color: 0,
+                skinid: m3dc::M3D_UNDEF,
+            }
+        }));
+        model.generated_normals = true;
+    }
+
+    fn bone_matrices(&mut self) {
+        let model = &mut self.model;
+        for i in 0..model.bones.len() {
+            let BoneData {
+                parent,
+                position,
+                orientation,
+                ..
+            } = model.bones[i];
+            let (pos, ori) = match (
+                model.vertices.get(position as usize),
+                model.vertices.get(orientation as usize),
+            ) {
+                (Some(pos), Some(ori)) => (pos, ori),
+                _ => {
+                    self.warnings.push(ErrorKind::Bone.into());
+                    continue;
+                }
+            };
+
+            let local = mat_from(pos, ori);
+            model.bones[i].mat4 = if parent == m3dc::M3D_UNDEF {
+                local
+            } else if (parent as usize) < i {
+                mat_mul(&model.bones[parent as usize].mat4, &local)
+            } else {
+                self.warnings.push(ErrorKind::Bone.into());
+                local
+            };
+        }
+        for bone in &mut model.bones {
+            bone.mat4 = mat_inv(&bone.mat4);
+        }
+    }
+
+    fn bone_weights(&mut self) {
+        let model = &mut self.model;
+        for (i, vertex) in model.vertices.iter().enumerate() {
+            let skin = match model.skins.get_mut(vertex.skinid as usize) {
+                Some(skin) => skin,
+                None => continue,
+            };
+
+            let used = skin
+                .boneid
+                .iter()
+                .zip(skin.weight)
+                .take_while(|(b, w)| **b != m3dc::M3D_UNDEF && *w > 0.0)
+                .count();
+            let total: f32 = skin.weight[..used].iter().sum();
+            for j in 0..used {
+                skin.weight[j] /= total;
+                if let Some(bone) = model.bones.get_mut(skin.boneid[j] as usize) {
+                    bone.weights.push(Weight {
+                        vertexid: i as _,
+                        weight: skin.weight[j],
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if len > 0.0 {
+        v.map(|c| c / len)
+    } else {
+        v
+    }
+}
+
+fn decode_png(bytes: &[u8]) -> Option<GeneratedTexture> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().ok()?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).ok()?;
+    pixels.truncate(info.buffer_size());
+
+    let format = match info.color_type {
+        png::ColorType::Grayscale => TextureFormat::Grayscale,
+        png::ColorType::GrayscaleAlpha => TextureFormat::GrayscaleAndAlpha,
+        png::ColorType::Rgb => TextureFormat::RGB,
+        png::ColorType::Rgba => TextureFormat::RGBA,
+        png::ColorType::Indexed => return None,
+    };
+
+    Some(GeneratedTexture {
+        width: info.width.try_into().ok()?,
+        height: info.height.try_into().ok()?,
+        format,
+        pixels,
+    })
+    .filter(GeneratedTexture::is_valid)
+}
@@ -0,0 +1,1062 @@
+use super::*;
+use std::collections::HashMap;
+
+#[derive(Default)]
+struct Writer {
+    data: Vec<u8>,
+}
+
+impl Writer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, v: u8) {
+        self.data.push(v);
+    }
+
+    fn u16(&mut self, v: u16) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn f32(&mut self, v: f32) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn f64(&mut self, v: f64) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    // Narrow indices keep their low bytes, which turns `M3D_UNDEF` into the negative values `_m3d_getidx` expects.
+    fn idx(&mut self, size: u8, v: u32) {
+        match size {
+            0 => {}
+            1 => self.u8(v as u8),
+            2 => self.u16(v as u16),
+            _ => self.u32(v),
+        }
+    }
+
+    fn coord(&mut self, size: u8, v: f32) {
+        match size {
+            1 => self.u8((v.clamp(-1.0, 1.0) * 127.0).round() as i8 as u8),
+            2 => self.u16((v.clamp(-1.0, 1.0) * 32767.0).round() as i16 as u16),
+            4 => self.f32(v),
+            _ => self.f64(v as f64),
+        }
+    }
+
+    fn unsigned_coord(&mut self, size: u8, v: f32) {
+        match size {
+            1 => self.u8((v.clamp(0.0, 1.0) * 255.0).round() as u8),
+            2 => self.u16((v.clamp(0.0, 1.0) * 65535.0).round() as u16),
+            4 => self.f32(v),
+            _ => self.f64(v as f64),
+        }
+    }
+
+    fn signed(&mut self, size: u8, v: i32) {
+        match size {
+            1 => self.u8(v as i8 as u8),
+            2 => self.u16(v as i16 as u16),
+            _ => self.u32(v as u32),
+        }
+    }
+
+    fn begin(&mut self, magic: &[u8; 4]) -> usize {
+        let start = self.data.len();
+        self.bytes(magic);
+        self.u32(0);
+        start
+    }
+
+    fn end(&mut self, start: usize) -> Result<(), ErrorKind> {
+        let len = u32::try_from(self.data.len() - start).map_err(|_| ErrorKind::Truncating)?;
+        self.data[start + 4..start + 8].copy_from_slice(&len.to_le_bytes());
+        Ok(())
+    }
+}
+
+struct Strings {
+    table: Vec<u8>,
+    offsets: HashMap<String, u32>,
+}
+
+impl Strings {
+    fn new(data: &ModelData) -> Self {
+        let mut table = Vec::new();
+        for s in [&data.name, &data.license, &data.author, &data.desc] {
+            table.extend_from_slice(c_str(s).as_bytes());
+            table.push(0);
+        }
+        Self {
+            table,
+            offsets: HashMap::new(),
+        }
+    }
+
+    // Offset zero means no string, so empty strings are never stored.
+    fn write(&mut self, w: &mut Writer, size: u8, s: &str) {
+        let s = c_str(s);
+        let offset = if s.is_empty() {
+            0
+        } else if let Some(&offset) = self.offsets.get(s) {
+            offset
+        } else {
+            let offset = self.table.len() as u32;
+            self.table.extend_from_slice(s.as_bytes());
+            self.table.push(0);
+            self.offsets.insert(s.to_string(), offset);
+            offset
+        };
+        w.idx(size, offset);
+    }
+}
+
+fn c_str(s: &str) -> &str {
+    s.split('\0').next().unwrap_or_default()
+}
+
+#[derive(Default)]
+struct Colors {
+    cmap: Vec<u32>,
+    index: HashMap<u32, u32>,
+}
+
+impl Colors {
+    fn write(&mut self, w: &mut Writer, size: u8, color: u32) {
+        let next = self.cmap.len() as u32;
+        let idx = *self.index.entry(color).or_insert(next);
+        if idx == next {
+            self.cmap.push(color);
+        }
+
+        match size {
+            0 => {}
+            4 => w.u32(color),
+            _ => w.idx(size, idx),
+        }
+    }
+
+    fn size(&self, no_cmap: bool) -> u8 {
+        if self.cmap.iter().all(|&c| c == 0) {
+            0
+        } else if no_cmap || self.cmap.len() > 65536 {
+            4
+        } else if self.cmap.len() > 256 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Kind {
+    Position,
+    // Normals and orientation quaternions, which are never scaled.
+    Direction,
+}
+
+#[derive(Default)]
+struct Remap {
+    vertices: Vec<Vertex>,
+    vertex_ids: HashMap<(u32, Kind), u32>,
+    texture_maps: Vec<TextureMapIndex>,
+    texture_map_ids: HashMap<u32, u32>,
+}
+
+impl Remap {
+    fn vertex(&self, id: u32, kind: Kind) -> u32 {
+        self.vertex_ids
+            .get(&(id, kind))
+            .copied()
+            .unwrap_or(m3dc::M3D_UNDEF)
+    }
+
+    fn texture_map(&self, id: u32) -> u32 {
+        self.texture_map_ids
+            .get(&id)
+            .copied()
+            .unwrap_or(m3dc::M3D_UNDEF)
+    }
+}
+
+struct Encoder<'a> {
+    data: &'a ModelData,
+    flags: SaveFlags,
+    sizes: Sizes,
+    strings: Strings,
+    colors: Colors,
+    remap: Remap,
+    materials: Vec<bool>,
+    // Argument types of every shape command, in model order.
+    arg_types: Vec<Vec<u32>>,
+    // Textures inlined from their pixels, encoded once for both passes.
+    pngs: Vec<(&'a str, Vec<u8>)>,
+}
+
+pub(crate) fn encode(
+    data: &ModelData,
+    quality: QuantizeQuality,
+    mut flags: SaveFlags,
+) -> Result<Vec<u8>, ErrorKind> {
+    if flags.contains(SaveFlags::ASCII) {
+        return ascii::write(data, flags);
+    }
+    // Normals generated on load are generated again by the reader.
+    if data.generated_normals {
+        flags |= SaveFlags::NO_NORMAL;
+    }
+
+    let arg_types = data
+        .shapes
+        .iter()
+        .flat_map(|s| &s.commands)
+        .map(|cmd| {
+            command_arg_types(cmd.kind, &cmd.args).ok_or(
+                if (cmd.kind as usize) < COMMAND_TYPES.len() {
+                    ErrorKind::Shape
+                } else {
+                    ErrorKind::UnknownCmd
+                },
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Textures already inlined as assets keep them, the others are encoded from their pixels.
+    let mut pngs = Vec::new();
+    if flags.contains(SaveFlags::INLINE) {
+        for texture in &data.textures {
+            let inlined = data.inlined.iter().any(|i| i.name == texture.name);
+            if !texture.pixels.is_empty() && !texture.name.is_empty() && !inlined {
+                pngs.push((texture.name.as_str(), encode_png(texture)?));
+            }
+        }
+    }
+
+    let vc = match quality {
+        QuantizeQuality::Int8 => 1,
+        QuantizeQuality::Int16 => 2,
+        QuantizeQuality::F32 => 4,
+        QuantizeQuality::F64 => 8,
+    };
+    let mut encoder = Encoder {
+        data,
+        flags,
+        sizes: Sizes {
+            vc,
+            vi: 4,
+            si: 4,
+            ci: 4,
+            ti: 4,
+            bi: 4,
+            nb: 1,
+            sk: 4,
+            fc: 4,
+            hi: 4,
+            fi: 4,
+            vd: 4,
+            vp: 2,
+        },
+        strings: Strings::new(data),
+        colors: Colors::default(),
+        remap: Remap::default(),
+        materials: Vec::new(),
+        arg_types,
+        pngs,
+    };
+
+    let scale = encoder.prepare_vertices()?;
+    encoder.prepare_texture_maps()?;
+    encoder.select_materials();
+    encoder.prepare_sizes()?;
+
+    // The first pass collects strings and colors, the second one writes them with their final sizes.
+    encoder.chunks()?;
+    encoder.sizes.si = idx_size(encoder.strings.table.len());
+    encoder.sizes.ci = encoder.colors.size(flags.contains(SaveFlags::NO_CMAP));
+    let chunks = encoder.chunks()?;
+
+    let mut body = Writer::default();
+    let start = body.begin(b"HEAD");
+    body.f32(scale);
+    body.u32(encoder.sizes.to_types());
+    body.bytes(&encoder.strings.table);
+    body.end(start)?;
+    body.bytes(&chunks);
+    body.bytes(b"OMD3");
+
+    let body = if flags.contains(SaveFlags::NO_ZLIB) {
+        body.data
+    } else {
+        miniz_oxide::deflate::compress_to_vec_zlib(&body.data, 9)
+    };
+
+    let mut out = Writer::default();
+    let start = out.begin(b"3DMO");
+    if !data.preview.is_empty() {
+        let preview = out.begin(b"PRVW");
+        out.bytes(&data.preview);
+        out.end(preview)?;
+    }
+    out.bytes(&body);
+    out.end(start)?;
+    Ok(out.data)
+}
+
+fn encode_png(texture: &TextureData) -> Result<Vec<u8>, ErrorKind> {
+    let color = match texture.format {
+        TextureFormat::Grayscale => png::ColorType::Grayscale,
+        TextureFormat::GrayscaleAndAlpha => png::ColorType::GrayscaleAlpha,
+        TextureFormat::RGB => png::ColorType::Rgb,
+        TextureFormat::RGBA => png::ColorType::Rgba,
+        TextureFormat::Invalid => return Err(ErrorKind::UnknownImg),
+    };
+    let len = texture.width as usize * texture.height as usize * texture.format as usize;
+    if texture.pixels.len() != len || len == 0 {
+        return Err(ErrorKind::UnknownImg);
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, texture.width as _, texture.height as _);
+    encoder.set_color(color);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|_| ErrorKind::UnknownImg)?;
+    writer
+        .write_image_data(&texture.pixels)
+        .map_err(|_| ErrorKind::UnknownImg)?;
+    writer.finish().map_err(|_| ErrorKind::UnknownImg)?;
+    Ok(png)
+}
+
+// Narrow indices lose their two topmost values to negative numbers.
+fn idx_size(count: usize) -> u8 {
+    if count < 254 {
+        1
+    } else if count < 65534 {
+        2
+    } else {
+        4
+    }
+}
+
+fn opt_idx_size(count: usize) -> u8 {
+    if count == 0 {
+        0
+    } else {
+        idx_size(count)
+    }
+}
+
+fn quantize(v: f32, size: u8) -> f32 {
+    let v = match size {
+        1 => (v.clamp(-1.0, 1.0) * 127.0).round() / 127.0,
+        2 => (v.clamp(-1.0, 1.0) * 32767.0).round() / 32767.0,
+        _ => v,
+    };
+    // Keeps 0.0 and -0.0 from becoming separate vertices.
+    if v == 0.0 {
+        0.0
+    } else {
+        v
+    }
+}
+
+fn quantize_unsigned(v: f32, size: u8) -> f32 {
+    match size {
+        1 => (v.clamp(0.0, 1.0) * 255.0).round() / 255.0,
+        2 => (v.clamp(0.0, 1.0) * 65535.0).round() / 65535.0,
+        _ => v,
+    }
+}
+
+impl<'a> Encoder<'a> {
+    fn faces(&self) -> bool {
+        !self.flags.contains(SaveFlags::NO_FACE)
+    }
+
+    fn normals(&self) -> bool {
+        !self.flags.contains(SaveFlags::NO_NORMAL)
+    }
+
+    fn bones(&self) -> bool {
+        !self.flags.contains(SaveFlags::NO_BONE) && !self.data.bones.is_empty()
+    }
+
+    fn actions(&self) -> bool {
+        self.bones() && !self.flags.contains(SaveFlags::NO_ACTION)
+    }
+
+    fn shape_args(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.data
+            .shapes
+            .iter()
+            .flat_map(|s| &s.commands)
+            .zip(&self.arg_types)
+            .flat_map(|(cmd, types)| types.iter().copied().zip(cmd.args.iter().copied()))
+    }
+
+    fn skin_id(&self, id: u32) -> u32 {
+        if self.bones() && (id as usize) < self.data.skins.len() {
+            id
+        } else {
+            m3dc::M3D_UNDEF
+        }
+    }
+
+    // Only vertices something refers to are written, in the order they are first referenced.
+    fn vertex_refs(&self) -> Result<Vec<(u32, Kind)>, ErrorKind> {
+        let data = self.data;
+        let mut refs = Vec::new();
+        if self.faces() {
+            for face in &data.faces {
+                refs.extend(face.vertex.map(|v| (v, Kind::Position)));
+                if self.normals() && face.normal.iter().all(|&n| n != m3dc::M3D_UNDEF) {
+                    refs.extend(face.normal.map(|n| (n, Kind::Direction)));
+                }
+            }
+        }
+        for (ty, arg) in self.shape_args() {
+            match ty {
+                VI => refs.push((arg, Kind::Position)),
+                QI => refs.push((arg, Kind::Direction)),
+                _ => {}
+            }
+        }
+        refs.extend(data.labels.iter().map(|l| (l.vertex_id, Kind::Position)));
+        if self.bones() {
+            for bone in &data.bones {
+                refs.push((bone.position, Kind::Position));
+                refs.push((bone.orientation, Kind::Direction));
+            }
+        }
+        if self.actions() {
+            for transform in data
+                .actions
+                .iter()
+                .flat_map(|a| &a.frames)
+                .flat_map(|f| &f.transforms)
+            {
+                refs.push((transform.pos, Kind::Position));
+                refs.push((transform.ori, Kind::Direction));
+            }
+        }
+
+        refs.retain(|&(id, _)| id != m3dc::M3D_UNDEF);
+        if refs
+            .iter()
+            .any(|&(id, _)| id as usize >= data.vertices.len())
+        {
+            return Err(ErrorKind::Vertices);
+        }
+        Ok(refs)
+    }
+
+    fn prepare_vertices(&mut self) -> Result<f32, ErrorKind> {
+        let refs = self.vertex_refs()?;
+        let vertices = &self.data.vertices;
+
+        // Coordinates are stored in the unit cube, bigger models are scaled down into it.
+        let extent = refs
+            .iter()
+            .filter(|(_, kind)| *kind == Kind::Position)
+            .map(|&(id, _)| {
+                let v = &vertices[id as usize];
+                v.x.abs().max(v.y.abs()).max(v.z.abs())
+            })
+            .fold(0.0, f32::max);
+        let divisor = if extent > 1.0 && !self.flags.contains(SaveFlags::NO_RECALC) {
+            extent
+        } else {
+            1.0
+        };
+
+        let vc = self.sizes.vc;
+        let mut unique = HashMap::new();
+        for (id, kind) in refs {
+            if self.remap.vertex_ids.contains_key(&(id, kind)) {
+                continue;
+            }
+
+            let v = &vertices[id as usize];
+            let d = if kind == Kind::Position { divisor } else { 1.0 };
+            let vertex = Vertex {
+                x: quantize(v.x / d, vc),
+                y: quantize(v.y / d, vc),
+                z: quantize(v.z / d, vc),
+                w: quantize(v.w, vc),
+                color: v.color,
+                skinid: self.skin_id(v.skinid),
+            };
+            let key = [
+                vertex.x.to_bits(),
+                vertex.y.to_bits(),
+                vertex.z.to_bits(),
+                vertex.w.to_bits(),
+                vertex.color,
+                vertex.skinid,
+            ];
+
+            let next = self.remap.vertices.len() as u32;
+            let new_id = *unique.entry(key).or_insert(next);
+            if new_id == next {
+                self.remap.vertices.push(vertex);
+            }
+            self.remap.vertex_ids.insert((id, kind), new_id);
+        }
+
+        let scale = if self.data.scale > 0.0 {
+            self.data.scale
+        } else {
+            1.0
+        };
+        Ok(scale * divisor)
+    }
+
+    fn prepare_texture_maps(&mut self) -> Result<(), ErrorKind> {
+        let data = self.data;
+        let mut refs = Vec::new();
+        if self.faces() && !self.flags.contains(SaveFlags::NO_TEXCOORD) {
+            for face in &data.faces {
+                if face.texcoord.iter().all(|&t| t != m3dc::M3D_UNDEF) {
+                    refs.extend(face.texcoord);
+                }
+            }
+        }
+        refs.extend(
+            self.shape_args()
+                .filter(|&(ty, arg)| ty == TI && arg != m3dc::M3D_UNDEF)
+                .map(|(_, arg)| arg),
+        );
+        if refs
+            .iter()
+            .any(|&id| id as usize >= data.texture_maps.len())
+        {
+            return Err(ErrorKind::TextureMap);
+        }
+
+        let vc = self.sizes.vc;
+        let flip = self.flags.contains(SaveFlags::FLIP_TEXCOORD);
+        let mut unique = HashMap::new();
+        for id in refs {
+            if self.remap.texture_map_ids.contains_key(&id) {
+                continue;
+            }
+
+            let uv = &data.texture_maps[id as usize];
+            let uv = TextureMapIndex {
+                u: quantize_unsigned(uv.u, vc),
+                v: quantize_unsigned(if flip { 1.0 - uv.v } else { uv.v }, vc),
+            };
+            let next = self.remap.texture_maps.len() as u32;
+            let new_id = *unique
+                .entry([uv.u.to_bits(), uv.v.to_bits()])
+                .or_insert(next);
+            if new_id == next {
+                self.remap.texture_maps.push(uv);
+            }
+            self.remap.texture_map_ids.insert(id, new_id);
+        }
+        Ok(())
+    }
+
+    fn select_materials(&mut self) {
+        let data = self.data;
+        self.materials = vec![false; data.materials.len()];
+        if self.flags.contains(SaveFlags::NO_MATERIAL) {
+            return;
+        }
+
+        // Material libraries have nothing referencing their materials.
+        if data.faces.is_empty() && data.voxel_types.is_empty() && data.shapes.is_empty() {
+            self.materials.iter_mut().for_each(|m| *m = true);
+            return;
+        }
+
+        let mut used: Vec<u32> = data.voxel_types.iter().map(|v| v.material_id).collect();
+        if self.faces() {
+            used.extend(data.faces.iter().map(|f| f.materialid));
+        }
+        used.extend(
+            self.shape_args()
+                .filter(|&(ty, _)| ty == MI)
+                .map(|(_, arg)| arg),
+        );
+        for id in used {
+            if let Some(selected) = self.materials.get_mut(id as usize) {
+                *selected = true;
+            }
+        }
+    }
+
+    fn prepare_sizes(&mut self) -> Result<(), ErrorKind> {
+        let data = self.data;
+        let (faces, bones, actions) = (self.faces(), self.bones(), self.actions());
+        let s = &mut self.sizes;
+        s.vi = idx_size(self.remap.vertices.len());
+        s.ti = opt_idx_size(self.remap.texture_maps.len());
+        s.hi = opt_idx_size(data.shapes.len());
+        s.fi = if faces {
+            opt_idx_size(data.faces.len())
+        } else {
+            0
+        };
+        s.bi = if bones { idx_size(data.bones.len()) } else { 0 };
+        s.sk = if bones {
+            opt_idx_size(data.skins.len())
+        } else {
+            0
+        };
+        let weights = data
+            .skins
+            .iter()
+            .map(|skin| skin_weights(skin).len())
+            .max()
+            .unwrap_or(1);
+        s.nb = if bones {
+            weights.clamp(1, m3dc::M3D_NUMBONE as _).next_power_of_two() as u8
+        } else {
+            1
+        };
+
+        let transforms = data
+            .actions
+            .iter()
+            .flat_map(|a| &a.frames)
+            .map(|f| f.transforms.len())
+            .max()
+            .unwrap_or(0);
+        s.fc = if actions { opt_idx_size(transforms) } else { 0 };
+
+        let signed_size = |v: i32| {
+            if i8::try_from(v).is_ok() {
+                1
+            } else if i16::try_from(v).is_ok() {
+                2
+            } else {
+                4
+            }
+        };
+        let unsigned_size = |v: u32| {
+            if v <= u8::MAX as u32 {
+                1
+            } else if v <= u16::MAX as u32 {
+                2
+            } else {
+                4
+            }
+        };
+        s.vd = data
+            .voxels
+            .iter()
+            .flat_map(|b| {
+                [b.x, b.y, b.z]
+                    .map(signed_size)
+                    .into_iter()
+                    .chain([b.width, b.height, b.depth].map(unsigned_size))
+            })
+            .max()
+            .unwrap_or(1);
+        s.vp = match data.voxel_types.len() {
+            n if n < 254 => 1,
+            n if n < 65534 => 2,
+            _ => return Err(ErrorKind::VoxelType),
+        };
+        Ok(())
+    }
+
+    fn chunks(&mut self) -> Result<Vec<u8>, ErrorKind> {
+        let mut w = Writer::default();
+        if matches!(self.sizes.ci, 1 | 2) {
+            let start = w.begin(b"CMAP");
+            for &color in &self.colors.cmap {
+                w.u32(color);
+            }
+            w.end(start)?;
+        }
+
+        self.write_texture_maps(&mut w)?;
+        self.write_vertices(&mut w)?;
+        self.write_bones(&mut w)?;
+        self.write_materials(&mut w)?;
+        self.write_mesh(&mut w)?;
+        self.write_voxels(&mut w)?;
+        self.write_shapes(&mut w)?;
+        self.write_labels(&mut w)?;
+        self.write_actions(&mut w)?;
+
+        let data = self.data;
+        if self.flags.contains(SaveFlags::INLINE) {
+            let inlined = data.inlined.iter().map(|i| (i.name.as_str(), &i.data[..]));
+            let pngs = self.pngs.iter().map(|(name, png)| (*name, &png[..]));
+            for (name, asset) in inlined.chain(pngs).filter(|(name, _)| !name.is_empty()) {
+                let start = w.begin(b"ASET");
+                self.strings.write(&mut w, self.sizes.si, name);
+                w.bytes(asset);
+                w.end(start)?;
+            }
+        }
+        if self.flags.contains(SaveFlags::EXTRA) {
+            for chunk in &data.extra {
+                let len = chunk
+                    .get(4..8)
+                    .map(|l| u32::from_le_bytes(l.try_into().unwrap()));
+                if len != Some(chunk.len() as u32) {
+                    return Err(ErrorKind::BadFile);
+                }
+                w.bytes(chunk);
+            }
+        }
+        Ok(w.data)
+    }
+
+    fn write_texture_maps(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        if self.remap.texture_maps.is_empty() {
+            return Ok(());
+        }
+
+        let start = w.begin(b"TMAP");
+        for uv in &self.remap.texture_maps {
+            w.unsigned_coord(self.sizes.vc, uv.u);
+            w.unsigned_coord(self.sizes.vc, uv.v);
+        }
+        w.end(start)
+    }
+
+    fn write_vertices(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        if self.remap.vertices.is_empty() {
+            return Ok(());
+        }
+
+        let s = self.sizes;
+        let start = w.begin(b"VRTS");
+        for v in &self.remap.vertices {
+            w.coord(s.vc, v.x);
+            w.coord(s.vc, v.y);
+            w.coord(s.vc, v.z);
+            w.coord(s.vc, v.w);
+            self.colors.write(w, s.ci, v.color);
+            w.idx(s.sk, v.skinid);
+        }
+        w.end(start)
+    }
+
+    fn write_bones(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        if !self.bones() {
+            return Ok(());
+        }
+
+        let data = self.data;
+        let s = self.sizes;
+        let skins = if s.sk > 0 { &data.skins[..] } else { &[] };
+        let start = w.begin(b"BONE");
+        w.idx(s.bi, data.bones.len() as _);
+        w.idx(s.sk, skins.len() as _);
+        for bone in &data.bones {
+            w.idx(s.bi, bone.parent);
+            self.strings.write(w, s.si, &bone.name);
+            w.idx(s.vi, self.remap.vertex(bone.position, Kind::Position));
+            w.idx(s.vi, self.remap.vertex(bone.orientation, Kind::Direction));
+        }
+        for skin in skins {
+            let weights = skin_weights(skin);
+            if s.nb == 1 {
+                w.idx(s.bi, weights.first().map_or(m3dc::M3D_UNDEF, |w| w.0));
+                continue;
+            }
+            for j in 0..s.nb as usize {
+                w.u8(weights.get(j).map_or(0, |w| w.1));
+            }
+            for &(boneid, _) in &weights {
+                w.idx(s.bi, boneid);
+            }
+        }
+        w.end(start)
+    }
+
+    fn write_materials(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        let data = self.data;
+        let s = self.sizes;
+        for (material, _) in data
+            .materials
+            .iter()
+            .zip(&self.materials)
+            .filter(|(_, &selected)| selected)
+        {
+            let start = w.begin(b"MTRL");
+            self.strings.write(w, s.si, &material.name);
+            for prop in &material.props {
+                let format = property_format(prop.type_).ok_or(ErrorKind::UnkownProperty)?;
+                if format == PF_MAP {
+                    // Like the C SDK, properties of missing textures are dropped.
+                    let texture = data.textures.get(unsafe { prop.value.textureid } as usize);
+                    if let Some(texture) = texture {
+                        w.u8(prop.type_);
+                        self.strings.write(w, s.si, &texture.name);
+                    }
+                    continue;
+                }
+
+                w.u8(prop.type_);
+                match format {
+                    PF_COLOR => self.colors.write(w, s.ci, unsafe { prop.value.color }),
+                    PF_UINT8 => w.u8(unsafe { prop.value.num } as u8),
+                    PF_UINT16 => w.u16(unsafe { prop.value.num } as u16),
+                    PF_UINT32 => w.u32(unsafe { prop.value.num }),
+                    _ => w.f32(unsafe { prop.value.fnum }),
+                }
+            }
+            w.end(start)?;
+        }
+        Ok(())
+    }
+
+    fn material_name(&self, id: u32) -> &'a str {
+        match self.data.materials.get(id as usize) {
+            Some(material) if self.materials[id as usize] => &material.name,
+            _ => "",
+        }
+    }
+
+    fn write_mesh(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        let data = self.data;
+        if !self.faces() || data.faces.is_empty() {
+            return Ok(());
+        }
+
+        let s = self.sizes;
+        let normals = self.normals();
+        let flip = self.flags.contains(SaveFlags::IDOSUCK);
+        let start = w.begin(b"MESH");
+        let mut materialid = "";
+        for face in &data.faces {
+            let mut face = *face;
+            // Reverses the winding order.
+            if flip {
+                face.vertex.swap(1, 2);
+                face.normal.swap(1, 2);
+                face.texcoord.swap(1, 2);
+            }
+
+            let name = self.material_name(face.materialid);
+            if materialid != name {
+                w.u8(0);
+                self.strings.write(w, s.si, name);
+                materialid = name;
+            }
+
+            let texcoord = face
+                .texcoord
+                .iter()
+                .all(|t| self.remap.texture_map_ids.contains_key(t));
+            let normal = normals && face.normal.iter().all(|&n| n != m3dc::M3D_UNDEF);
+            w.u8(3 << 4 | texcoord as u8 | (normal as u8) << 1);
+            for j in 0..3 {
+                w.idx(s.vi, self.remap.vertex(face.vertex[j], Kind::Position));
+                if texcoord {
+                    w.idx(s.ti, self.remap.texture_map(face.texcoord[j]));
+                }
+                if normal {
+                    w.idx(s.vi, self.remap.vertex(face.normal[j], Kind::Direction));
+                }
+            }
+        }
+        w.end(start)
+    }
+
+    fn write_voxels(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        let data = self.data;
+        let s = self.sizes;
+        if !data.voxel_types.is_empty() {
+            let start = w.begin(b"VOXT");
+            for voxel in &data.voxel_types {
+                self.colors.write(w, s.ci, voxel.color);
+                // The name refers to the material, when there is one.
+                let name = match self.material_name(voxel.material_id) {
+                    "" => &voxel.name,
+                    name => name,
+                };
+                self.strings.write(w, s.si, name);
+                w.u8((voxel.rotation & 0xBF) | (((voxel.shape >> 8) & 1) as u8) << 6);
+                w.u8(voxel.shape as u8);
+                let items = &voxel.items[..voxel.items.len().min(u8::MAX as usize)];
+                w.u8(items.len() as u8);
+                w.idx(s.sk, self.skin_id(voxel.skin_id));
+                for item in items {
+                    w.u16(item.count);
+                    self.strings.write(w, s.si, &item.name);
+                }
+            }
+            w.end(start)?;
+        }
+
+        for block in &data.voxels {
+            let len = (block.width as usize)
+                .checked_mul(block.height as _)
+                .and_then(|l| l.checked_mul(block.depth as _));
+            if len != Some(block.data.len()) {
+                return Err(ErrorKind::UnknownkVoxex);
+            }
+
+            let start = w.begin(b"VOXD");
+            self.strings.write(w, s.si, &block.name);
+            w.signed(s.vd, block.x);
+            w.signed(s.vd, block.y);
+            w.signed(s.vd, block.z);
+            w.idx(s.vd, block.width);
+            w.idx(s.vd, block.height);
+            w.idx(s.vd, block.depth);
+            w.u8(block.uncertain);
+            w.u8(block.group);
+            write_runs(w, s.vp, &block.data);
+            w.end(start)?;
+        }
+        Ok(())
+    }
+
+    fn write_shapes(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        let data = self.data;
+        let s = self.sizes;
+        let mut arg_types = self.arg_types.iter();
+        for shape in &data.shapes {
+            let start = w.begin(b"SHPE");
+            self.strings.write(w, s.si, &shape.name);
+            let group = if (shape.group as usize) < data.bones.len() {
+                shape.group
+            } else {
+                m3dc::M3D_UNDEF
+            };
+            w.idx(s.bi, group);
+
+            for (cmd, types) in shape.commands.iter().zip(&mut arg_types) {
+                if cmd.kind > 0x7F {
+                    w.u8((cmd.kind & 0x7F) as u8 | 0x80);
+                    w.u8((cmd.kind >> 7) as u8);
+                } else {
+                    w.u8(cmd.kind as u8);
+                }
+
+                for (&ty, &arg) in types.iter().zip(&cmd.args) {
+                    match ty {
+                        MI => {
+                            let name = self.material_name(arg);
+                            self.strings.write(w, s.si, name);
+                        }
+                        VC => w.coord(s.vc, f32::from_bits(arg)),
+                        HI => w.idx(s.hi, arg),
+                        FI => w.idx(s.fi, arg),
+                        TI => w.idx(s.ti, self.remap.texture_map(arg)),
+                        VI => w.idx(s.vi, self.remap.vertex(arg, Kind::Position)),
+                        QI => w.idx(s.vi, self.remap.vertex(arg, Kind::Direction)),
+                        I1 => w.u8(arg as u8),
+                        I2 => w.u16(arg as u16),
+                        _ => w.u32(arg),
+                    }
+                }
+            }
+            w.end(start)?;
+        }
+        Ok(())
+    }
+
+    fn write_labels(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        let s = self.sizes;
+        let mut labels = &self.data.labels[..];
+        // Consecutive labels sharing a name, language and color go in one chunk.
+        while let Some(first) = labels.first() {
+            let len = labels
+                .iter()
+                .take_while(|l| {
+                    l.name == first.name && l.lang == first.lang && l.color == first.color
+                })
+                .count();
+
+            let start = w.begin(b"LBLS");
+            self.strings.write(w, s.si, &first.name);
+            self.strings.write(w, s.si, &first.lang);
+            self.colors.write(w, s.ci, first.color);
+            for label in &labels[..len] {
+                w.idx(s.vi, self.remap.vertex(label.vertex_id, Kind::Position));
+                self.strings.write(w, s.si, &label.text);
+            }
+            w.end(start)?;
+            labels = &labels[len..];
+        }
+        Ok(())
+    }
+
+    fn write_actions(&mut self, w: &mut Writer) -> Result<(), ErrorKind> {
+        if !self.actions() {
+            return Ok(());
+        }
+
+        let s = self.sizes;
+        for action in self.data.actions.iter().filter(|a| !a.frames.is_empty()) {
+            let numframe =
+                u16::try_from(action.frames.len()).map_err(|_| ErrorKind::UnknownFrame)?;
+            let start = w.begin(b"ACTN");
+            self.strings.write(w, s.si, &action.name);
+            w.u16(numframe);
+            w.u32(action.duration_msec);
+            for frame in &action.frames {
+                w.u32(frame.msec);
+                w.idx(s.fc, frame.transforms.len() as _);
+                for transform in &frame.transforms {
+                    w.idx(s.bi, transform.boneid);
+                    w.idx(s.vi, self.remap.vertex(transform.pos, Kind::Position));
+                    w.idx(s.vi, self.remap.vertex(transform.ori, Kind::Direction));
+                }
+            }
+            w.end(start)?;
+        }
+        Ok(())
+    }
+}
+
+// Bone ids with their weights scaled to bytes, zero weights are not stored.
+fn skin_weights(skin: &Skin) -> Vec<(u32, u8)> {
+    skin.boneid
+        .iter()
+        .zip(skin.weight)
+        .filter(|(&boneid, _)| boneid != m3dc::M3D_UNDEF)
+        .map(|(&boneid, weight)| (boneid, (weight.clamp(0.0, 1.0) * 255.0).round() as u8))
+        .filter(|&(_, weight)| weight > 0)
+        .collect()
+}
+
+// Run-length encoding of voxel data, runs and literal spans hold at most 128 voxels.
+fn write_runs(w: &mut Writer, size: u8, data: &[u16]) {
+    let mut i = 0;
+    while i < data.len() {
+        let run = data[i..]
+            .iter()
+            .take(128)
+            .take_while(|&&v| v == data[i])
+            .count();
+        if run > 1 {
+            w.u8(0x80 | (run - 1) as u8);
+            w.idx(size, data[i] as _);
+            i += run;
+            continue;
+        }
+
+        // Literal spans stop where the next run starts.
+        let mut len = 1;
+        while len < 128 && i + len < data.len() {
+            if i + len + 1 < data.len() && data[i + len] == data[i + len + 1] {
+                break;
+            }
+            len += 1;
+        }
+        w.u8((len - 1) as u8);
+        for &v in &data[i..i + len] {
+            w.idx(size, v as _);
+        }
+        i += len;
+    }
+}
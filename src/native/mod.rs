@@ -0,0 +1,345 @@
+// Only the format tables are left with `double` and `small-index`, the rest works on `ModelData`.
+#![cfg_attr(any(feature = "double", feature = "small-index"), allow(dead_code))]
+
+use super::*;
+
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+pub(crate) mod ascii;
+#[cfg(feature = "pure-rust")]
+mod decode;
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+mod encode;
+
+#[cfg(feature = "pure-rust")]
+pub(crate) use decode::decode;
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+pub(crate) use encode::encode;
+
+pub(crate) struct PropertyDef {
+    pub format: u32,
+    pub id: u8,
+    pub name: &'static str,
+}
+
+const fn prop(format: u32, id: u8, name: &'static str) -> PropertyDef {
+    PropertyDef { format, id, name }
+}
+
+// Bindgen types the enum constants as signed or unsigned depending on the target.
+pub(crate) const PF_COLOR: u32 = m3dc::m3dpf_color as _;
+pub(crate) const PF_UINT8: u32 = m3dc::m3dpf_uint8 as _;
+pub(crate) const PF_UINT16: u32 = m3dc::m3dpf_uint16 as _;
+pub(crate) const PF_UINT32: u32 = m3dc::m3dpf_uint32 as _;
+pub(crate) const PF_FLOAT: u32 = m3dc::m3dpf_float as _;
+pub(crate) const PF_MAP: u32 = m3dc::m3dpf_map as _;
+
+// Mirrors `m3d_propertytypes` from m3d.h, the aliases come last.
+pub(crate) const PROPERTY_TYPES: [PropertyDef; 17] = [
+    prop(PF_COLOR, m3dc::m3dp_Kd as _, "Kd"),
+    prop(PF_COLOR, m3dc::m3dp_Ka as _, "Ka"),
+    prop(PF_COLOR, m3dc::m3dp_Ks as _, "Ks"),
+    prop(PF_FLOAT, m3dc::m3dp_Ns as _, "Ns"),
+    prop(PF_COLOR, m3dc::m3dp_Ke as _, "Ke"),
+    prop(PF_COLOR, m3dc::m3dp_Tf as _, "Tf"),
+    prop(PF_FLOAT, m3dc::m3dp_Km as _, "Km"),
+    prop(PF_FLOAT, m3dc::m3dp_d as _, "d"),
+    prop(PF_UINT8, m3dc::m3dp_il as _, "il"),
+    prop(PF_FLOAT, m3dc::m3dp_Pr as _, "Pr"),
+    prop(PF_FLOAT, m3dc::m3dp_Pm as _, "Pm"),
+    prop(PF_FLOAT, m3dc::m3dp_Ps as _, "Ps"),
+    prop(PF_FLOAT, m3dc::m3dp_Ni as _, "Ni"),
+    prop(PF_FLOAT, m3dc::m3dp_Nt as _, "Nt"),
+    prop(PF_MAP, m3dc::m3dp_bump as _, "bump"),
+    prop(PF_MAP, m3dc::m3dp_map_N as _, "map_N"),
+    prop(PF_MAP, m3dc::m3dp_refl as _, "refl"),
+];
+
+pub(crate) fn property_format(id: u8) -> Option<u32> {
+    if id >= 128 {
+        return Some(PF_MAP);
+    }
+    PROPERTY_TYPES.iter().find(|p| p.id == id).map(|p| p.format)
+}
+
+pub(crate) struct CommandDef {
+    pub name: &'static str,
+    pub args: &'static [u32],
+}
+
+const fn cmd(name: &'static str, args: &'static [u32]) -> CommandDef {
+    CommandDef { name, args }
+}
+
+const MI: u32 = m3dc::m3dcp_mi_t as _;
+const HI: u32 = m3dc::m3dcp_hi_t as _;
+const FI: u32 = m3dc::m3dcp_fi_t as _;
+const TI: u32 = m3dc::m3dcp_ti_t as _;
+const VI: u32 = m3dc::m3dcp_vi_t as _;
+const QI: u32 = m3dc::m3dcp_qi_t as _;
+const VC: u32 = m3dc::m3dcp_vc_t as _;
+const I1: u32 = m3dc::m3dcp_i1_t as _;
+const I2: u32 = m3dc::m3dcp_i2_t as _;
+const VA: u32 = m3dc::m3dcp_va_t as _;
+
+// Mirrors `m3d_commandtypes` from m3d.h, indexed by the `m3dc_*` command ids.
+pub(crate) const COMMAND_TYPES: [CommandDef; 38] = [
+    cmd("use", &[MI]),
+    cmd("inc", &[HI, VI, QI, VI]),
+    cmd("mesh", &[FI, FI, FI, FI, VI, QI, VI]),
+    cmd("div", &[VC]),
+    cmd("sub", &[VC, VC]),
+    cmd("len", &[VC]),
+    cmd("dist", &[VC, VC]),
+    cmd("degu", &[I1]),
+    cmd("deg", &[I1, I1]),
+    cmd("rangeu", &[TI]),
+    cmd("range", &[TI, TI]),
+    cmd("paru", &[VA, VC]),
+    cmd("parv", &[VA, VC]),
+    cmd("trim", &[VA, TI, I2]),
+    cmd("hole", &[VA, TI, I2]),
+    cmd("scrv", &[VA, TI, I2]),
+    cmd("sp", &[VA, VI]),
+    cmd("bez1", &[VA, VI]),
+    cmd("bsp1", &[VA, VI]),
+    cmd("bez2", &[VA, VI]),
+    cmd("bsp2", &[VA, VI]),
+    cmd("bezun", &[VA, VI, TI, VI]),
+    cmd("bezu", &[VA, VI, TI]),
+    cmd("bezn", &[VA, VI, VI]),
+    cmd("bez", &[VA, VI]),
+    cmd("nurbsun", &[VA, VI, TI, VI]),
+    cmd("nurbsu", &[VA, VI, TI]),
+    cmd("nurbsn", &[VA, VI, VI]),
+    cmd("nurbs", &[VA, VI]),
+    cmd("conn", &[I2, TI, I2, I2, TI, I2]),
+    cmd("line", &[VA, VI]),
+    cmd("polygon", &[VA, VI]),
+    cmd("circle", &[VI, QI, VC]),
+    cmd("cylinder", &[VI, QI, VC, VI, QI, VC]),
+    cmd("shpere", &[VI, VC]),
+    cmd("torus", &[VI, QI, VC, VC]),
+    cmd("cone", &[VI, VI, VI]),
+    cmd("cube", &[VI, VI, VI]),
+];
+
+/// Argument types of a shape command, expanding the variadic tail to match `args`.
+pub(crate) fn command_arg_types(kind: u16, args: &[u32]) -> Option<Vec<u32>> {
+    let def = COMMAND_TYPES.get(kind as usize)?;
+    let p = def.args.len();
+    let mut types = Vec::with_capacity(args.len());
+    let (mut n, mut l) = (0, p);
+    while types.len() < l {
+        let k = types.len();
+        let arg = def.args[((k - n) % (p - n)) + n];
+        if arg == VA {
+            let count = *args.get(k)? as usize;
+            if count == 0 {
+                return None;
+            }
+            n = k + 1;
+            l = l.checked_add((count - 1).checked_mul(p - k - 1)?)?;
+            if l > args.len() {
+                return None;
+            }
+        }
+        types.push(arg);
+    }
+    (types.len() == args.len()).then_some(types)
+}
+
+/// Number of arguments a command stores, with variadic counts read through `arg`.
+pub(crate) fn command_arg_count(kind: u16, arg: impl Fn(usize) -> u32) -> Option<usize> {
+    let def = COMMAND_TYPES.get(kind as usize)?;
+    let p = def.args.len();
+    let (mut k, mut n, mut l) = (0, 0, p);
+    while k < l {
+        if def.args[((k - n) % (p - n)) + n] == VA {
+            let count = arg(k) as usize;
+            if count == 0 {
+                return None;
+            }
+            n = k + 1;
+            l = l.checked_add((count - 1).checked_mul(p - k - 1)?)?;
+        }
+        k += 1;
+    }
+    Some(l)
+}
+
+/// Byte sizes of the variable width fields, as packed in the header's `types`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Sizes {
+    pub vc: u8,
+    pub vi: u8,
+    pub si: u8,
+    pub ci: u8,
+    pub ti: u8,
+    pub bi: u8,
+    pub nb: u8,
+    pub sk: u8,
+    pub fc: u8,
+    pub hi: u8,
+    pub fi: u8,
+    pub vd: u8,
+    pub vp: u8,
+}
+
+impl Sizes {
+    pub fn from_types(types: u32) -> Self {
+        let size = |shift: u32| 1u8 << ((types >> shift) & 3);
+        // Size 8 marks optional indices which are not stored at all.
+        let opt = |shift: u32| match size(shift) {
+            8 => 0,
+            s => s,
+        };
+        Self {
+            vc: size(0),
+            vi: size(2),
+            si: size(4),
+            ci: opt(6),
+            ti: opt(8),
+            bi: opt(10),
+            nb: size(12),
+            sk: opt(14),
+            fc: opt(16),
+            hi: opt(18),
+            fi: opt(20),
+            vd: size(22),
+            vp: size(24),
+        }
+    }
+
+    pub fn to_types(self) -> u32 {
+        // Optional indices with a size of zero are stored as 8, meaning not present.
+        let code = |size: u8| match size {
+            1 => 0,
+            2 => 1,
+            4 => 2,
+            _ => 3,
+        };
+        [
+            self.vc, self.vi, self.si, self.ci, self.ti, self.bi, self.nb, self.sk, self.fc,
+            self.hi, self.fi, self.vd, self.vp,
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |types, (i, &size)| types | code(size) << (i * 2))
+    }
+}
+
+#[cfg(feature = "pure-rust")]
+pub(crate) const EPSILON: f32 = 1e-7;
+
+#[cfg(feature = "pure-rust")]
+/// Bone matrix from a position and an orientation quaternion, like `_m3d_mat`.
+pub(crate) fn mat_from(p: &Vertex, q: &Vertex) -> [f32; 16] {
+    let mut r = [0f32; 16];
+    if q.x == 0.0 && q.y == 0.0 && q.z >= 0.7071065 && q.z <= 0.7071075 && q.w == 0.0 {
+        r[0] = -1.0;
+        r[5] = -1.0;
+        r[10] = -1.0;
+    } else {
+        let snap = |v: f32| if v > -EPSILON && v < EPSILON { 0.0 } else { v };
+        r[0] = snap(1.0 - 2.0 * (q.y * q.y + q.z * q.z));
+        r[1] = snap(2.0 * (q.x * q.y - q.z * q.w));
+        r[2] = snap(2.0 * (q.x * q.z + q.y * q.w));
+        r[4] = snap(2.0 * (q.x * q.y + q.z * q.w));
+        r[5] = snap(1.0 - 2.0 * (q.x * q.x + q.z * q.z));
+        r[6] = snap(2.0 * (q.y * q.z - q.x * q.w));
+        r[8] = snap(2.0 * (q.x * q.z - q.y * q.w));
+        r[9] = snap(2.0 * (q.y * q.z + q.x * q.w));
+        r[10] = snap(1.0 - 2.0 * (q.x * q.x + q.y * q.y));
+    }
+    r[3] = p.x;
+    r[7] = p.y;
+    r[11] = p.z;
+    r[15] = 1.0;
+    r
+}
+
+#[cfg(feature = "pure-rust")]
+pub(crate) fn mat_mul(a: &[f32; 16], b: &[f32; 16]) -> [f32; 16] {
+    let mut r = [0f32; 16];
+    for row in 0..4 {
+        for col in 0..4 {
+            r[row * 4 + col] = (0..4).map(|k| a[row * 4 + k] * b[k * 4 + col]).sum();
+        }
+    }
+    r
+}
+
+#[cfg(feature = "pure-rust")]
+pub(crate) fn mat_inv(m: &[f32; 16]) -> [f32; 16] {
+    let mut inv = [0f32; 16];
+    inv[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15]
+        + m[9] * m[7] * m[14]
+        + m[13] * m[6] * m[11]
+        - m[13] * m[7] * m[10];
+    inv[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15]
+        - m[8] * m[7] * m[14]
+        - m[12] * m[6] * m[11]
+        + m[12] * m[7] * m[10];
+    inv[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15]
+        + m[8] * m[7] * m[13]
+        + m[12] * m[5] * m[11]
+        - m[12] * m[7] * m[9];
+    inv[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14]
+        - m[8] * m[6] * m[13]
+        - m[12] * m[5] * m[10]
+        + m[12] * m[6] * m[9];
+    inv[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15]
+        - m[9] * m[3] * m[14]
+        - m[13] * m[2] * m[11]
+        + m[13] * m[3] * m[10];
+    inv[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15]
+        + m[8] * m[3] * m[14]
+        + m[12] * m[2] * m[11]
+        - m[12] * m[3] * m[10];
+    inv[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15]
+        - m[8] * m[3] * m[13]
+        - m[12] * m[1] * m[11]
+        + m[12] * m[3] * m[9];
+    inv[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14]
+        + m[8] * m[2] * m[13]
+        + m[12] * m[1] * m[10]
+        - m[12] * m[2] * m[9];
+    inv[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15]
+        + m[5] * m[3] * m[14]
+        + m[13] * m[2] * m[7]
+        - m[13] * m[3] * m[6];
+    inv[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15]
+        - m[4] * m[3] * m[14]
+        - m[12] * m[2] * m[7]
+        + m[12] * m[3] * m[6];
+    inv[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15]
+        + m[4] * m[3] * m[13]
+        + m[12] * m[1] * m[7]
+        - m[12] * m[3] * m[5];
+    inv[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14]
+        - m[4] * m[2] * m[13]
+        - m[12] * m[1] * m[6]
+        + m[12] * m[2] * m[5];
+    inv[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11]
+        - m[5] * m[3] * m[10]
+        - m[9] * m[2] * m[7]
+        + m[9] * m[3] * m[6];
+    inv[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11]
+        + m[4] * m[3] * m[10]
+        + m[8] * m[2] * m[7]
+        - m[8] * m[3] * m[6];
+    inv[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11]
+        - m[4] * m[3] * m[9]
+        - m[8] * m[1] * m[7]
+        + m[8] * m[3] * m[5];
+    inv[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10]
+        + m[4] * m[2] * m[9]
+        + m[8] * m[1] * m[6]
+        - m[8] * m[2] * m[5];
+
+    let det = m[0] * inv[0] + m[1] * inv[4] + m[2] * inv[8] + m[3] * inv[12];
+    if det == 0.0 {
+        return *m;
+    }
+    inv.map(|v| v / det)
+}
@@ -1,130 +1,130 @@
 use super::*;
+#[cfg(all(not(feature = "pure-rust"), feature = "exporter"))]
+use std::io::Write;
+#[cfg(all(not(feature = "pure-rust"), feature = "exporter"))]
+use std::path::Path;
 
 #[repr(transparent)]
-#[derive(Debug, Clone)]
-pub struct Obj(m3dc::m3d_t);
-
-impl Drop for Obj {
-    fn drop(&mut self) {
-        unsafe {
-            let m3d_ptr = self as *mut object::Obj as *mut bindings::m3d_t;
-            //@TODO: Using libc to read file to buffer and free it, expose proper rust closures.
-            m3dfree_default(m3d_ptr as _);
-        }
-    }
-}
+#[derive(Debug)]
+pub struct Obj(pub(crate) m3dc::m3d_t);
 
 impl Obj {
-    pub fn load_from_u8<'a>(
-        data: &mut Vec<u8>,
-        mtllib: Option<&mut Obj>,
-    ) -> Result<&'a Obj, Error> {
-        let mttlib_c = match mtllib {
-            Some(mtl) => mtl as *mut Obj as *mut m3dc::m3d_t,
-            None => std::ptr::null_mut(),
-        };
-
-        //@TODO: Using libc to read file to buffer and free it, expose proper rust closures.
-        let m3d_c = unsafe {
-            m3dc::m3d_load(
-                data.as_mut_ptr(),
-                Some(m3dread_default),
-                Some(m3dfree_default),
-                mttlib_c,
-            )
-        };
-
-        if m3d_c.is_null() {
-            return Err(Error::ReturnedNull);
-        }
-
-        let err = unsafe { Error::from((*m3d_c).errcode) };
-        if err as i8 == m3dc::M3D_SUCCESS as i8 {
-            Ok(unsafe { &*(m3d_c as *const Obj) })
-        } else {
-            Err(err)
-        }
-    }
-
-    pub fn load_from_file<P: AsRef<std::path::Path>>(
-        path: P,
-        mtllib: Option<&mut Obj>,
-    ) -> Result<&Obj, Error> {
-        let result = std::fs::read(path);
-        match result {
-            Err(_) => Err(Error::ReturnedNull),
-            Ok(mut data) => Self::load_from_u8(&mut data, mtllib),
+    /// Encodes the model with the C SDK. The buffer `m3d_save` allocates is copied and freed.
+    ///
+    /// The SDK only inlines assets, textures which would have to be encoded from their pixels are
+    /// refused with [`ErrorKind::InvalidOptions`]. `ModelData::save` encodes those.
+    #[cfg(all(not(feature = "pure-rust"), feature = "exporter"))]
+    pub fn save(&self, options: SaveOptions) -> Result<Vec<u8>, Error> {
+        let (quality, flags) = options.to_flags()?;
+        if flags.contains(SaveFlags::INLINE) {
+            let inlined = self.inlined_textures();
+            for texture in self.textures() {
+                let name = texture.name();
+                if !texture.data().is_empty() && !inlined.iter().any(|i| i.name() == name) {
+                    return Err(Error::from(ErrorKind::InvalidOptions).with_asset(name));
+                }
+            }
         }
-    }
-
-    pub fn save(
-        &self,
-        quality: Option<QuantizeQuality>,
-        flags: Option<SaveFlags>,
-    ) -> Option<&[u8]> {
         let mut m3d_encoded_len = 0u32;
         let m3d_encoded = unsafe {
             m3d_save(
                 // Casting *const Object to *mut m3dc::m3d_t since only model::errorcode is modified in C
                 self as *const Obj as *mut m3dc::m3d_t,
-                quality.unwrap_or(QuantizeQuality::F32) as _,
-                if let Some(flags) = flags {
-                    flags.bits
-                } else {
-                    0
-                },
+                quality as _,
+                flags.bits,
                 &mut m3d_encoded_len as _,
             )
         };
 
         if m3d_encoded.is_null() {
-            None
+            // The SDK only leaves a code behind when an allocation failed.
+            match ErrorKind::from(self.0.errcode) {
+                ErrorKind::Alloc => Err(ErrorKind::Alloc.into()),
+                _ => Err(ErrorKind::ReturnedNull.into()),
+            }
         } else {
-            unsafe { Some(cptr_to_slice(m3d_encoded, m3d_encoded_len as _)) }
+            unsafe {
+                let encoded = cptr_to_slice(m3d_encoded, m3d_encoded_len as _).to_vec();
+                alloc::free(m3d_encoded as _);
+                Ok(encoded)
+            }
         }
     }
 
+    #[cfg(all(not(feature = "pure-rust"), feature = "exporter"))]
+    pub fn save_to_writer(
+        &self,
+        mut writer: impl Write,
+        options: SaveOptions,
+    ) -> Result<(), Error> {
+        writer.write_all(&self.save(options)?)?;
+        Ok(())
+    }
+
+    #[cfg(all(not(feature = "pure-rust"), feature = "exporter"))]
+    pub fn save_to_file(&self, path: impl AsRef<Path>, options: SaveOptions) -> Result<(), Error> {
+        let path = path.as_ref();
+        let encoded = self.save(options).map_err(|err| err.with_path(path))?;
+        std::fs::write(path, encoded).map_err(|err| Error::from(err).with_path(path))
+    }
+
+    /// Transforms of every bone at a key frame, applied on top of `skeleton` or the bind pose.
+    /// `None` when `skeleton` has fewer transforms than the model has bones.
+    #[cfg(all(not(feature = "pure-rust"), feature = "animation"))]
     pub fn frame(
         &self,
-        action_id: u32,
-        frame_id: u32,
+        action_id: Index,
+        frame_id: Index,
         skeleton: Option<&[Transform]>,
-    ) -> Option<&[Transform]> {
+    ) -> Option<Vec<Transform>> {
+        let numbone = self.0.numbone as usize;
+        // The SDK updates a given skeleton in place, so it works on a copy.
+        let skeleton = match skeleton {
+            Some(skeleton) if skeleton.len() < numbone => return None,
+            Some(skeleton) => unsafe {
+                let copy = alloc::malloc(numbone.max(1) * std::mem::size_of::<Transform>());
+                if copy.is_null() {
+                    return None;
+                }
+                std::ptr::copy_nonoverlapping(skeleton.as_ptr(), copy as *mut Transform, numbone);
+                copy as *mut m3dc::m3dtr_t
+            },
+            None => std::ptr::null_mut(),
+        };
         let transforms = unsafe {
             m3dc::m3d_frame(
                 self as *const Obj as *mut m3dc::m3d_t,
                 action_id,
                 frame_id,
-                if let Some(skeleton) = skeleton {
-                    &skeleton[0] as *const Transform as *mut m3dc::m3dtr_t
-                } else {
-                    std::ptr::null_mut()
-                },
+                skeleton,
             )
         };
 
         if transforms.is_null() {
             None
         } else {
-            assert!(self.0.numbone > 0);
             unsafe {
-                Some(cptr_to_slice(
-                    transforms as *const Transform,
-                    self.0.numbone as _,
-                ))
+                let frame = cptr_to_slice(transforms as *const Transform, numbone).to_vec();
+                alloc::free(transforms as _);
+                Some(frame)
             }
         }
     }
 
-    pub fn pose(&self, action_id: u32, msec: u32) -> Option<&[Bone]> {
+    /// Bones interpolated `msec` into an action.
+    #[cfg(all(not(feature = "pure-rust"), feature = "animation"))]
+    pub fn pose(&self, action_id: Index, msec: u32) -> Option<Vec<Bone>> {
         let bones =
             unsafe { m3dc::m3d_pose(self as *const Obj as *mut m3dc::m3d_t, action_id, msec) };
 
         if bones.is_null() {
             None
         } else {
-            assert!(self.0.numbone > 0);
-            unsafe { Some(cptr_to_slice(bones as *const Bone, self.0.numbone as _)) }
+            unsafe {
+                let pose = cptr_to_slice(bones as *const Bone, self.0.numbone as _).to_vec();
+                alloc::free(bones as _);
+                Some(pose)
+            }
         }
     }
 
@@ -152,6 +152,11 @@ impl Obj {
         unsafe { cptr_to_slice(self.0.cmap, self.0.numcmap as _) }
     }
 
+    /// Colors are resolved on load, this is for indices into [`Obj::color_maps`] found elsewhere.
+    pub fn resolve_color(&self, index: Index) -> Option<Color> {
+        Color::from_color_map(self.color_maps(), index)
+    }
+
     pub fn texture_maps(&self) -> &[TextureMapIndex] {
         unsafe { cptr_to_slice(self.0.tmap, self.0.numtmap as _) }
     }
@@ -188,11 +193,32 @@ impl Obj {
         unsafe { std::mem::transmute::<_, &[Action]>(slice) }
     }
 
+    pub fn voxel_types(&self) -> &[VoxelType] {
+        let slice = unsafe { cptr_to_slice(self.0.voxtype, self.0.numvoxtype as _) };
+        unsafe { std::mem::transmute::<_, &[VoxelType]>(slice) }
+    }
+
+    pub fn voxels(&self) -> &[VoxelBlock] {
+        let slice = unsafe { cptr_to_slice(self.0.voxel, self.0.numvoxel as _) };
+        unsafe { std::mem::transmute::<_, &[VoxelBlock]>(slice) }
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        let slice = unsafe { cptr_to_slice(self.0.label, self.0.numlabel as _) };
+        unsafe { std::mem::transmute::<_, &[Label]>(slice) }
+    }
+
     pub fn inlined_textures(&self) -> &[InlinedTexture] {
         let slice = unsafe { cptr_to_slice(self.0.inlined, self.0.numinlined as _) };
         unsafe { std::mem::transmute::<_, &[InlinedTexture]>(slice) }
     }
 
+    /// Copies the model into an editable [`ModelData`], which can be saved or turned back into a [`Model`].
+    #[cfg(not(any(feature = "double", feature = "small-index")))]
+    pub fn to_data(&self) -> ModelData {
+        unsafe { data::import(&self.0) }
+    }
+
     pub fn inlined(&self) -> &InlinedTexture {
         unsafe { std::mem::transmute::<_, &InlinedTexture>(&self.0.preview) }
     }
@@ -0,0 +1,138 @@
+use super::*;
+
+/// How a renderer should treat the alpha of [`PbrMaterial::base_color`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AlphaMode {
+    Opaque,
+    /// Fully opaque or fully transparent, split at the cutoff.
+    Mask(f32),
+    Blend,
+}
+
+/// A metallic-roughness material, as glTF renderers expect it. Colors are linear RGB(A), maps are
+/// indices into the model's textures.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PbrMaterial {
+    pub base_color: [f32; 4],
+    pub base_color_map: Option<Index>,
+    pub metallic: f32,
+    pub metallic_map: Option<Index>,
+    pub roughness: f32,
+    pub roughness_map: Option<Index>,
+    pub emissive: [f32; 3],
+    pub emissive_map: Option<Index>,
+    pub normal_map: Option<Index>,
+    pub occlusion_map: Option<Index>,
+    pub alpha_mode: AlphaMode,
+    pub ior: f32,
+}
+
+impl Default for PbrMaterial {
+    fn default() -> Self {
+        Self {
+            base_color: [1.0; 4],
+            base_color_map: None,
+            metallic: 0.0,
+            metallic_map: None,
+            roughness: 1.0,
+            roughness_map: None,
+            emissive: [0.0; 3],
+            emissive_map: None,
+            normal_map: None,
+            occlusion_map: None,
+            alpha_mode: AlphaMode::Opaque,
+            ior: 1.5,
+        }
+    }
+}
+
+// Reflectance of dielectrics at normal incidence, the usual metallic-roughness assumption.
+const DIELECTRIC_SPECULAR: f32 = 0.04;
+
+impl Material {
+    /// Converts to metallic-roughness. `Pm` and `Pr` are used when present, otherwise metallic is
+    /// solved from the `Kd` and `Ks` brightness and roughness derived from the `Ns` exponent.
+    ///
+    /// A dissolve below one blends, a dissolve map alone is taken for a cutout and masks. The
+    /// ambient map is used as occlusion.
+    pub fn to_pbr(&self) -> PbrMaterial {
+        let mut pbr = PbrMaterial::default();
+        let diffuse = self.diffuse().map_or([1.0; 4], Color::to_linear);
+        pbr.base_color = diffuse;
+
+        match self.metallic() {
+            Some(metallic) => pbr.metallic = metallic.clamp(0.0, 1.0),
+            None => {
+                if let Some(specular) = self.specular().map(Color::to_linear) {
+                    let (metallic, base) = solve_metallic(diffuse, specular);
+                    pbr.metallic = metallic;
+                    pbr.base_color[..3].copy_from_slice(&base);
+                }
+            }
+        }
+        pbr.roughness = match (self.roughness(), self.specular_exponent()) {
+            (Some(roughness), _) => roughness.clamp(0.0, 1.0),
+            // Beckmann roughness matching a Blinn-Phong exponent.
+            (None, Some(exponent)) => (2.0 / (exponent.max(0.0) + 2.0)).sqrt(),
+            (None, None) => 1.0,
+        };
+        if let Some(emissive) = self.emissive().map(Color::to_linear) {
+            pbr.emissive.copy_from_slice(&emissive[..3]);
+        }
+        if let Some(ior) = self.refraction_index() {
+            pbr.ior = ior;
+        }
+
+        pbr.base_color_map = self.map(PropertyKind::Diffuse);
+        pbr.metallic_map = self.refl();
+        pbr.roughness_map = self.map(PropertyKind::Roughness);
+        pbr.emissive_map = self.map(PropertyKind::Emissive);
+        pbr.normal_map = self.map_il();
+        pbr.occlusion_map = self.map(PropertyKind::Ambient);
+
+        if let Some(dissolve) = self.dissolve() {
+            pbr.base_color[3] *= dissolve.clamp(0.0, 1.0);
+        }
+        pbr.alpha_mode = if pbr.base_color[3] < 1.0 {
+            AlphaMode::Blend
+        } else if self.map(PropertyKind::Dissolve).is_some() {
+            AlphaMode::Mask(0.5)
+        } else {
+            AlphaMode::Opaque
+        };
+        pbr
+    }
+}
+
+fn brightness(c: &[f32]) -> f32 {
+    (0.299 * c[0] * c[0] + 0.587 * c[1] * c[1] + 0.114 * c[2] * c[2]).sqrt()
+}
+
+// The specular-glossiness to metallic-roughness conversion from the glTF sample viewer.
+fn solve_metallic(diffuse: [f32; 4], specular: [f32; 4]) -> (f32, [f32; 3]) {
+    let one_minus_specular = 1.0 - specular[0].max(specular[1]).max(specular[2]);
+    let diffuse_brightness = brightness(&diffuse);
+    let specular_brightness = brightness(&specular);
+
+    let metallic = if specular_brightness < DIELECTRIC_SPECULAR {
+        0.0
+    } else {
+        let a = DIELECTRIC_SPECULAR;
+        let b = diffuse_brightness * one_minus_specular / (1.0 - a) + specular_brightness - 2.0 * a;
+        let c = a - specular_brightness;
+        let d = (b * b - 4.0 * a * c).max(0.0);
+        ((-b + d.sqrt()) / (2.0 * a)).clamp(0.0, 1.0)
+    };
+
+    let mut base = [0.0; 3];
+    for (i, base) in base.iter_mut().enumerate() {
+        let from_diffuse = diffuse[i] * one_minus_specular
+            / (1.0 - DIELECTRIC_SPECULAR)
+            / (1.0 - metallic).max(f32::EPSILON);
+        let from_specular =
+            (specular[i] - DIELECTRIC_SPECULAR * (1.0 - metallic)) / metallic.max(f32::EPSILON);
+        let t = metallic * metallic;
+        *base = (from_diffuse + (from_specular - from_diffuse) * t).clamp(0.0, 1.0);
+    }
+    (metallic, base)
+}
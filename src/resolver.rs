@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// Provides the bytes of external assets (textures, material libraries, scripts) by name.
+pub trait AssetResolver {
+    fn resolve(&self, name: &str) -> Option<Vec<u8>>;
+}
+
+impl<R: AssetResolver + ?Sized> AssetResolver for &R {
+    fn resolve(&self, name: &str) -> Option<Vec<u8>> {
+        (**self).resolve(name)
+    }
+}
+
+impl<R: AssetResolver + ?Sized> AssetResolver for Box<R> {
+    fn resolve(&self, name: &str) -> Option<Vec<u8>> {
+        (**self).resolve(name)
+    }
+}
+
+impl AssetResolver for HashMap<String, Vec<u8>> {
+    fn resolve(&self, name: &str) -> Option<Vec<u8>> {
+        self.get(name).cloned()
+    }
+}
+
+/// Resolves assets relative to a root directory, refusing names that escape it.
+#[derive(Debug, Clone)]
+pub struct DirResolver {
+    root: PathBuf,
+}
+
+impl DirResolver {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl AssetResolver for DirResolver {
+    fn resolve(&self, name: &str) -> Option<Vec<u8>> {
+        let name = Path::new(name);
+        if !name.components().all(|c| matches!(c, Component::Normal(_))) {
+            return None;
+        }
+
+        std::fs::read(self.root.join(name)).ok()
+    }
+}
+
+/// Tries each resolver in insertion order and returns the first hit.
+#[derive(Default)]
+pub struct ChainResolver<'a> {
+    resolvers: Vec<Box<dyn AssetResolver + 'a>>,
+}
+
+impl<'a> ChainResolver<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<R: AssetResolver + 'a>(mut self, resolver: R) -> Self {
+        self.push(resolver);
+        self
+    }
+
+    pub fn push<R: AssetResolver + 'a>(&mut self, resolver: R) {
+        self.resolvers.push(Box::new(resolver));
+    }
+}
+
+impl AssetResolver for ChainResolver<'_> {
+    fn resolve(&self, name: &str) -> Option<Vec<u8>> {
+        self.resolvers.iter().find_map(|r| r.resolve(name))
+    }
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+/// Whether a model is saved as binary chunks or as text.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Container {
+    #[default]
+    Binary,
+    /// Has no compression, quantization, inlined textures or extra chunks.
+    Ascii,
+}
+
+/// How the chunks of a binary model are stored.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    Zlib,
+    None,
+}
+
+/// Whether the `v` of texture coordinates is turned into `1 - v` on save.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum TexcoordFlip {
+    #[default]
+    None,
+    Vertical,
+}
+
+bitflags! {
+    /// Parts of the model left out of the file.
+    #[derive(Default)]
+    pub struct Strip : u8 {
+        const COLOR_MAP = 0b0000_0001;
+        const MATERIALS = 0b0000_0010;
+        const FACES = 0b0000_0100;
+        const NORMALS = 0b0000_1000;
+        const TEXCOORDS = 0b0001_0000;
+        /// Actions animate bones, so they go along.
+        const BONES = 0b0010_0000;
+        const ACTIONS = 0b0100_0000;
+    }
+}
+
+/// Configures how a model is encoded by the `save` methods.
+///
+/// Binary models default to zlib compression and [`QuantizeQuality::F32`] coordinates. Options
+/// which only apply to binary models are refused for ASCII ones with [`ErrorKind::InvalidOptions`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct SaveOptions {
+    container: Container,
+    compression: Option<Compression>,
+    quantization: Option<QuantizeQuality>,
+    strip: Strip,
+    texcoord_flip: TexcoordFlip,
+    reverse_winding: bool,
+    inline_textures: bool,
+    extra_chunks: bool,
+    keep_scale: bool,
+}
+
+impl SaveOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn container(mut self, container: Container) -> Self {
+        self.container = container;
+        self
+    }
+
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Precision of the vertex coordinates.
+    pub fn quantization(mut self, quantization: QuantizeQuality) -> Self {
+        self.quantization = Some(quantization);
+        self
+    }
+
+    pub fn strip(mut self, strip: Strip) -> Self {
+        self.strip = strip;
+        self
+    }
+
+    pub fn texcoord_flip(mut self, flip: TexcoordFlip) -> Self {
+        self.texcoord_flip = flip;
+        self
+    }
+
+    /// Turns clockwise faces into counterclockwise ones and back.
+    pub fn reverse_winding(mut self, reverse: bool) -> Self {
+        self.reverse_winding = reverse;
+        self
+    }
+
+    /// Embeds the model's inlined assets, and the pixels of textures without one as PNG.
+    pub fn inline_textures(mut self, inline: bool) -> Self {
+        self.inline_textures = inline;
+        self
+    }
+
+    /// Writes the model's unknown chunks back.
+    pub fn extra_chunks(mut self, extra: bool) -> Self {
+        self.extra_chunks = extra;
+        self
+    }
+
+    /// Stores coordinates as they are, instead of scaling bigger models down into the unit cube.
+    pub fn keep_scale(mut self, keep: bool) -> Self {
+        self.keep_scale = keep;
+        self
+    }
+
+    /// The quality and flags the encoders take, once the options are checked against each other.
+    #[cfg_attr(
+        all(
+            any(feature = "double", feature = "small-index"),
+            any(feature = "pure-rust", not(feature = "exporter"))
+        ),
+        allow(dead_code)
+    )]
+    pub(crate) fn to_flags(self) -> Result<(QuantizeQuality, SaveFlags), Error> {
+        let mut flags = SaveFlags::empty();
+        match self.container {
+            Container::Binary => {
+                if self.compression == Some(Compression::None) {
+                    flags |= SaveFlags::NO_ZLIB;
+                }
+                flags.set(SaveFlags::INLINE, self.inline_textures);
+                flags.set(SaveFlags::EXTRA, self.extra_chunks);
+            }
+            Container::Ascii => {
+                if self.compression.is_some()
+                    || self.quantization.is_some()
+                    || self.inline_textures
+                    || self.extra_chunks
+                {
+                    return Err(ErrorKind::InvalidOptions.into());
+                }
+                flags |= SaveFlags::ASCII;
+            }
+        }
+
+        let strip = [
+            (Strip::COLOR_MAP, SaveFlags::NO_CMAP),
+            (Strip::MATERIALS, SaveFlags::NO_MATERIAL),
+            (Strip::FACES, SaveFlags::NO_FACE),
+            (Strip::NORMALS, SaveFlags::NO_NORMAL),
+            (Strip::TEXCOORDS, SaveFlags::NO_TEXCOORD),
+            (Strip::BONES, SaveFlags::NO_BONE | SaveFlags::NO_ACTION),
+            (Strip::ACTIONS, SaveFlags::NO_ACTION),
+        ];
+        for (part, flag) in strip {
+            if self.strip.contains(part) {
+                flags |= flag;
+            }
+        }
+        flags.set(
+            SaveFlags::FLIP_TEXCOORD,
+            self.texcoord_flip == TexcoordFlip::Vertical,
+        );
+        flags.set(SaveFlags::IDOSUCK, self.reverse_winding);
+        flags.set(SaveFlags::NO_RECALC, self.keep_scale);
+
+        Ok((self.quantization.unwrap_or(QuantizeQuality::F32), flags))
+    }
+}
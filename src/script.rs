@@ -0,0 +1,303 @@
+use super::*;
+#[cfg(not(feature = "pure-rust"))]
+use std::ffi::CString;
+
+/// Pixels produced by a [`TextureScriptHandler`], `pixels` holds `width * height * format` bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedTexture {
+    pub width: u16,
+    pub height: u16,
+    pub format: TextureFormat,
+    pub pixels: Vec<u8>,
+}
+
+impl GeneratedTexture {
+    pub fn is_valid(&self) -> bool {
+        self.format != TextureFormat::Invalid
+            && self.pixels.len()
+                == (self.width as usize) * (self.height as usize) * (self.format as usize)
+    }
+}
+
+/// Generates the texture for a procedural texture script referenced by a model.
+pub trait TextureScriptHandler {
+    fn generate(&self, name: &str, script: &[u8]) -> Option<GeneratedTexture>;
+}
+
+impl<F: Fn(&str, &[u8]) -> Option<GeneratedTexture>> TextureScriptHandler for F {
+    fn generate(&self, name: &str, script: &[u8]) -> Option<GeneratedTexture> {
+        self(name, script)
+    }
+}
+
+/// Generates geometry for a procedural surface script referenced by a model.
+pub trait ProceduralModelHandler {
+    fn generate(&self, name: &str, script: &[u8], model: &mut ProceduralModel)
+        -> Result<(), Error>;
+}
+
+impl<F: Fn(&str, &[u8], &mut ProceduralModel) -> Result<(), Error>> ProceduralModelHandler for F {
+    fn generate(
+        &self,
+        name: &str,
+        script: &[u8],
+        model: &mut ProceduralModel,
+    ) -> Result<(), Error> {
+        self(name, script, model)
+    }
+}
+
+/// Appends data to the model being loaded from inside a [`ProceduralModelHandler`].
+pub struct ProceduralModel<'a> {
+    target: Target<'a>,
+}
+
+enum Target<'a> {
+    #[cfg(not(feature = "pure-rust"))]
+    #[cfg_attr(not(feature = "importer"), allow(dead_code))]
+    Raw {
+        model: &'a mut m3dc::m3d_t,
+        strings: &'a mut Vec<CString>,
+    },
+    #[cfg(feature = "pure-rust")]
+    Data(&'a mut ModelData),
+}
+
+impl<'a> ProceduralModel<'a> {
+    #[cfg(not(feature = "pure-rust"))]
+    #[cfg_attr(not(feature = "importer"), allow(dead_code))]
+    pub(crate) fn new(model: &'a mut m3dc::m3d_t, strings: &'a mut Vec<CString>) -> Self {
+        Self {
+            target: Target::Raw { model, strings },
+        }
+    }
+
+    #[cfg(feature = "pure-rust")]
+    pub(crate) fn from_data(model: &'a mut ModelData) -> Self {
+        Self {
+            target: Target::Data(model),
+        }
+    }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        match &self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => unsafe {
+                cptr_to_slice(model.vertex, model.numvertex as _)
+            },
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => &model.vertices,
+        }
+    }
+
+    pub fn texture_maps(&self) -> &[TextureMapIndex] {
+        match &self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => unsafe { cptr_to_slice(model.tmap, model.numtmap as _) },
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => &model.texture_maps,
+        }
+    }
+
+    pub fn faces(&self) -> &[Face] {
+        match &self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => unsafe { cptr_to_slice(model.face, model.numface as _) },
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => &model.faces,
+        }
+    }
+
+    pub fn material_count(&self) -> usize {
+        match &self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => model.nummaterial as _,
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => model.materials.len(),
+        }
+    }
+
+    fn texture_count(&self) -> usize {
+        match &self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => model.numtexture as _,
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => model.textures.len(),
+        }
+    }
+
+    pub fn material_id(&self, name: &str) -> Option<Index> {
+        let id = match &self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => {
+                let slice = unsafe { cptr_to_slice(model.material, model.nummaterial as _) };
+                let materials =
+                    unsafe { std::mem::transmute::<&[m3dc::m3dm_t], &[Material]>(slice) };
+                materials.iter().position(|m| m.name() == name)
+            }
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => model.materials.iter().position(|m| m.name == name),
+        };
+        id.map(|i| i as _)
+    }
+
+    pub fn add_vertex(&mut self, vertex: Vertex) -> Result<Index, Error> {
+        match &mut self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => unsafe {
+                push_c(&mut model.vertex, &mut model.numvertex, vertex)
+            },
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => push_vec(&mut model.vertices, vertex),
+        }
+    }
+
+    pub fn add_texture_map(&mut self, uv: TextureMapIndex) -> Result<Index, Error> {
+        match &mut self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => unsafe { push_c(&mut model.tmap, &mut model.numtmap, uv) },
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => push_vec(&mut model.texture_maps, uv),
+        }
+    }
+
+    /// Adds a material unless one with that name exists, properties are set like
+    /// [`ModelBuilder::set_material_property`] does.
+    pub fn add_material(
+        &mut self,
+        name: &str,
+        props: &[(PropertyKind, PropertyValue)],
+    ) -> Result<Index, Error> {
+        if props.len() > u8::MAX as usize {
+            return Err(ErrorKind::Material.into());
+        }
+        if let Some(id) = self.material_id(name) {
+            return Ok(id);
+        }
+        let textures = self.texture_count();
+        let props = props
+            .iter()
+            .map(|&(kind, value)| value.to_prop(kind, textures))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match &mut self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, strings } => unsafe {
+                add_material_c(model, strings, name, &props)
+            },
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => push_vec(
+                &mut model.materials,
+                MaterialData {
+                    name: name.to_string(),
+                    props,
+                },
+            ),
+        }
+    }
+
+    pub fn add_face(&mut self, face: Face) -> Result<Index, Error> {
+        let in_range = |idx: Index, len: usize, optional: bool| {
+            (idx as usize) < len || (optional && idx == m3dc::M3D_UNDEF as Index)
+        };
+        let numvertex = self.vertices().len();
+        let valid = in_range(face.materialid, self.material_count(), true)
+            && face.vertex.iter().all(|&i| in_range(i, numvertex, false))
+            && face.normal.iter().all(|&i| in_range(i, numvertex, true))
+            && face
+                .texcoord
+                .iter()
+                .all(|&i| in_range(i, self.texture_maps().len(), true));
+        if !valid {
+            return Err(ErrorKind::UnknownMesh.into());
+        }
+
+        match &mut self.target {
+            #[cfg(not(feature = "pure-rust"))]
+            Target::Raw { model, .. } => unsafe {
+                push_c(&mut model.face, &mut model.numface, face)
+            },
+            #[cfg(feature = "pure-rust")]
+            Target::Data(model) => push_vec(&mut model.faces, face),
+        }
+    }
+}
+
+#[cfg(feature = "pure-rust")]
+fn push_vec<T>(items: &mut Vec<T>, value: T) -> Result<Index, Error> {
+    if items.len() >= m3dc::M3D_INDEXMAX as usize {
+        return Err(ErrorKind::Truncating.into());
+    }
+    items.push(value);
+    Ok((items.len() - 1) as _)
+}
+
+#[cfg(not(feature = "pure-rust"))]
+unsafe fn add_material_c(
+    model: &mut m3dc::m3d_t,
+    strings: &mut Vec<CString>,
+    name: &str,
+    props: &[MaterialProp],
+) -> Result<Index, Error> {
+    // Materials borrowed from a material library are not ours to grow.
+    if model.flags as u32 & m3dc::M3D_FLG_MTLLIB != 0 {
+        return Err(ErrorKind::Material.into());
+    }
+
+    let mut prop_c = std::ptr::null_mut::<MaterialProp>();
+    let mut numprop: Index = 0;
+    for prop in props {
+        if let Err(err) = push_c(&mut prop_c, &mut numprop, *prop) {
+            alloc::free(prop_c as _);
+            return Err(err);
+        }
+    }
+
+    let freestr = model.flags as u32 & m3dc::M3D_FLG_FREESTR != 0;
+    let name_c = match CString::new(name) {
+        // m3d_free releases the strings itself.
+        Ok(name) if freestr => alloc::strdup(&name),
+        Ok(name) => {
+            let ptr = name.as_ptr() as *mut libc::c_char;
+            strings.push(name);
+            ptr
+        }
+        Err(_) => std::ptr::null_mut(),
+    };
+
+    let material = m3dc::m3dm_t {
+        name: name_c,
+        numprop: numprop as _,
+        prop: prop_c,
+    };
+    let result = if name_c.is_null() {
+        Err(ErrorKind::Material.into())
+    } else {
+        push_c(&mut model.material, &mut model.nummaterial, material)
+    };
+    if result.is_err() {
+        alloc::free(prop_c as _);
+        // m3d_free only releases the name of a material it holds.
+        if freestr {
+            alloc::free(name_c as _);
+        }
+    }
+    result
+}
+
+#[cfg(not(feature = "pure-rust"))]
+unsafe fn push_c<T: Copy>(array: &mut *mut T, len: &mut Index, value: T) -> Result<Index, Error> {
+    let idx = *len;
+    if idx >= m3dc::M3D_INDEXMAX as Index {
+        return Err(ErrorKind::Truncating.into());
+    }
+
+    let ptr = alloc::realloc(*array as _, (idx as usize + 1) * std::mem::size_of::<T>()) as *mut T;
+    if ptr.is_null() {
+        return Err(ErrorKind::Alloc.into());
+    }
+    ptr.add(idx as _).write(value);
+    *array = ptr;
+    *len = idx + 1;
+    Ok(idx)
+}
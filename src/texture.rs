@@ -1,10 +1,10 @@
 use super::*;
 use num_enum::FromPrimitive;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, FromPrimitive)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, FromPrimitive)]
 #[repr(u8)]
 pub enum TextureFormat {
-    #[num_enum(default)]
+    #[default]
     Invalid = 0,
     Grayscale = 1,
     GrayscaleAndAlpha = 2,
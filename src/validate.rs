@@ -0,0 +1,166 @@
+use super::*;
+
+// `M3D_UNDEF` narrowed to the configured index width.
+const UNDEF: Index = Index::MAX;
+
+fn check(id: Index, len: usize, kind: ErrorKind) -> Result<(), Error> {
+    if (id as usize) < len {
+        Ok(())
+    } else {
+        Err(kind.into())
+    }
+}
+
+fn check_or_undef(id: Index, len: usize, kind: ErrorKind) -> Result<(), Error> {
+    if id == UNDEF {
+        Ok(())
+    } else {
+        check(id, len, kind)
+    }
+}
+
+impl Obj {
+    /// Checks every index the views and the SDK follow without bounds checks, so that a file
+    /// claiming more than it has is rejected instead of read out of bounds.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        let m = &self.0;
+        let (vertices, bones) = (m.numvertex as usize, m.numbone as usize);
+
+        for vertex in self.vertices() {
+            check_or_undef(vertex.skinid, m.numskin as _, ErrorKind::Vertices)?;
+        }
+        for skin in self.skins() {
+            for &bone in &skin.boneid {
+                check_or_undef(bone, bones, ErrorKind::Bone)?;
+            }
+        }
+        for face in self.faces() {
+            check_or_undef(face.materialid, m.nummaterial as _, ErrorKind::Material)
+                .map_err(|err| err.with_chunk("MESH"))?;
+            for j in 0..3 {
+                check(face.vertex[j], vertices, ErrorKind::Vertices)
+                    .and_then(|_| check_or_undef(face.normal[j], vertices, ErrorKind::Vertices))
+                    .and_then(|_| {
+                        check_or_undef(face.texcoord[j], m.numtmap as _, ErrorKind::TextureMap)
+                    })
+                    .map_err(|err| err.with_chunk("MESH"))?;
+            }
+        }
+        for bone in self.bones() {
+            check_or_undef(bone.parent(), bones, ErrorKind::Bone)
+                .and_then(|_| check(bone.position(), vertices, ErrorKind::Bone))
+                .and_then(|_| check(bone.orientation(), vertices, ErrorKind::Bone))
+                .map_err(|err| err.with_chunk("BONE"))?;
+            for weight in bone.weights() {
+                check(weight.vertexid, vertices, ErrorKind::Bone)
+                    .map_err(|err| err.with_chunk("BONE"))?;
+            }
+        }
+        for material in self.materials() {
+            for prop in material.props().iter().filter(|p| p.type_ >= 128) {
+                let texture = unsafe { prop.value.textureid };
+                check_or_undef(texture, m.numtexture as _, ErrorKind::UnknownImg)
+                    .map_err(|err| err.with_chunk("MTRL"))?;
+            }
+        }
+        for texture in self.textures() {
+            let len = texture.width() as u64 * texture.height() as u64 * texture.format() as u64;
+            if len > u32::MAX as u64 {
+                return Err(ErrorKind::UnknownImg.into());
+            }
+        }
+        for action in self.actions() {
+            for frame in action.frames() {
+                for transform in frame.transforms() {
+                    check(transform.boneid, bones, ErrorKind::UnknownFrame)
+                        .and_then(|_| check(transform.pos, vertices, ErrorKind::UnknownFrame))
+                        .and_then(|_| check(transform.ori, vertices, ErrorKind::UnknownFrame))
+                        .map_err(|err| err.with_chunk("ACTN"))?;
+                }
+            }
+        }
+
+        let labels = unsafe { cptr_to_slice(m.label, m.numlabel as _) };
+        for label in labels {
+            check(label.vertexid, vertices, ErrorKind::Vertices)
+                .map_err(|err| err.with_chunk("LBLS"))?;
+        }
+        // The arguments are only as long as the counts among them say, which the SDK read them by.
+        // Unknown commands are skipped with a warning and have none.
+        let shapes = unsafe { cptr_to_slice(m.shape, m.numshape as _) };
+        for command in shapes
+            .iter()
+            .flat_map(|s| unsafe { cptr_to_slice(s.cmd, s.numcmd as _) })
+        {
+            if command.type_ as usize >= native::COMMAND_TYPES.len() {
+                continue;
+            }
+            let count = (!command.arg.is_null())
+                .then(|| {
+                    native::command_arg_count(command.type_, |k| unsafe { *command.arg.add(k) })
+                })
+                .flatten();
+            if count.is_none() {
+                return Err(Error::from(ErrorKind::Shape).with_chunk("SHPE"));
+            }
+        }
+        let voxel_types = unsafe { cptr_to_slice(m.voxtype, m.numvoxtype as _) };
+        for voxel_type in voxel_types {
+            check_or_undef(
+                voxel_type.materialid,
+                m.nummaterial as _,
+                ErrorKind::VoxelType,
+            )
+            .and_then(|_| check_or_undef(voxel_type.skinid, m.numskin as _, ErrorKind::VoxelType))
+            .map_err(|err| err.with_chunk("VOXT"))?;
+        }
+        let voxels = unsafe { cptr_to_slice(m.voxel, m.numvoxel as _) };
+        // The SDK allocates `w * h * d` voxels in 32 bits, `VoxelBlock::data` trusts that count.
+        for block in voxels {
+            let len = block
+                .w
+                .checked_mul(block.h)
+                .and_then(|l| l.checked_mul(block.d));
+            let allocated = matches!(len, Some(len) if len == 0 || !block.data.is_null());
+            if !allocated {
+                return Err(Error::from(ErrorKind::UnknownkVoxex).with_chunk("VOXD"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(feature = "double", feature = "small-index")))]
+impl ModelData {
+    /// Checks what the exported arrays are sized by, which the C structs don't store themselves.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        for command in self.shapes.iter().flat_map(|s| &s.commands) {
+            if native::command_arg_types(command.kind, &command.args).is_none() {
+                let kind = match native::COMMAND_TYPES.get(command.kind as usize) {
+                    Some(_) => ErrorKind::Shape,
+                    None => ErrorKind::UnknownCmd,
+                };
+                return Err(Error::from(kind).with_chunk("SHPE"));
+            }
+        }
+        for block in &self.voxels {
+            let len = (block.width as usize)
+                .checked_mul(block.height as _)
+                .and_then(|l| l.checked_mul(block.depth as _));
+            if len != Some(block.data.len()) {
+                return Err(Error::from(ErrorKind::UnknownkVoxex).with_chunk("VOXD"));
+            }
+        }
+        // Extra chunks are copied as is, their header has to tell their length.
+        for chunk in &self.extra {
+            let len = chunk
+                .get(4..8)
+                .map(|l| u32::from_le_bytes(l.try_into().unwrap()));
+            if len.map(|l| l as usize) != Some(chunk.len()) {
+                let magic = String::from_utf8_lossy(&chunk[..chunk.len().min(4)]);
+                return Err(Error::from(ErrorKind::BadFile).with_chunk(&magic));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use super::*;
+
+mod mesh;
+
+pub use mesh::VoxelMesh;
+
+#[repr(transparent)]
+#[derive(Debug, Clone)]
+pub struct VoxelItem(m3dc::m3dvi_t);
+
+impl VoxelItem {
+    pub fn count(&self) -> u16 {
+        self.0.count
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe { cptr_to_str(self.0.name) }
+    }
+}
+
+/// What a voxel is made of. Voxel blocks refer to these by index.
+#[repr(transparent)]
+#[derive(Debug, Clone)]
+pub struct VoxelType(m3dc::m3dvt_t);
+
+impl VoxelType {
+    pub fn name(&self) -> &str {
+        unsafe { cptr_to_str(self.0.name) }
+    }
+
+    pub fn rotation(&self) -> u8 {
+        self.0.rotation
+    }
+
+    pub fn shape(&self) -> u16 {
+        self.0.voxshape
+    }
+
+    /// `M3D_UNDEF` when the voxel is only colored.
+    pub fn material(&self) -> Index {
+        self.0.materialid
+    }
+
+    pub fn color(&self) -> Color {
+        Color(self.0.color)
+    }
+
+    pub fn skin(&self) -> Index {
+        self.0.skinid
+    }
+
+    pub fn items(&self) -> &[VoxelItem] {
+        let slice = unsafe { cptr_to_slice(self.0.item, self.0.numitem as _) };
+        unsafe { std::mem::transmute::<_, &[VoxelItem]>(slice) }
+    }
+}
+
+/// A voxel of a [`VoxelBlock`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Voxel {
+    /// Nothing is known about the space, `M3D_VOXUNDEF`.
+    Undefined,
+    /// Known to be empty, `M3D_VOXCLEAR`.
+    Clear,
+    /// An index into the model's voxel types.
+    Type(Index),
+}
+
+impl From<m3dc::M3D_VOXEL> for Voxel {
+    fn from(voxel: m3dc::M3D_VOXEL) -> Self {
+        match voxel as u32 {
+            m3dc::M3D_VOXUNDEF => Voxel::Undefined,
+            m3dc::M3D_VOXCLEAR => Voxel::Clear,
+            id => Voxel::Type(id as _),
+        }
+    }
+}
+
+/// A box of voxels placed in the model's voxel grid.
+#[repr(transparent)]
+#[derive(Debug, Clone)]
+pub struct VoxelBlock(m3dc::m3dvx_t);
+
+impl VoxelBlock {
+    pub fn name(&self) -> &str {
+        unsafe { cptr_to_str(self.0.name) }
+    }
+
+    /// The grid position of the block's first voxel.
+    pub fn position(&self) -> [i32; 3] {
+        [self.0.x, self.0.y, self.0.z]
+    }
+
+    /// Width, height and depth, along x, y and z.
+    pub fn size(&self) -> [u32; 3] {
+        [self.0.w, self.0.h, self.0.d]
+    }
+
+    /// The probability of the voxels being there, 0 when certain.
+    pub fn uncertain(&self) -> u8 {
+        self.0.uncertain
+    }
+
+    pub fn group(&self) -> u8 {
+        self.0.groupid
+    }
+
+    /// Raw voxels, layer by layer along y, each layer row by row along z.
+    pub fn data(&self) -> &[m3dc::M3D_VOXEL] {
+        // Blocks only come from validated models, whose voxel data holds exactly this many.
+        let len = self.0.w as usize * self.0.h as usize * self.0.d as usize;
+        unsafe { cptr_to_slice(self.0.data, len) }
+    }
+
+    /// The voxel at a position relative to the block, `None` outside of it.
+    pub fn get(&self, x: u32, y: u32, z: u32) -> Option<Voxel> {
+        let [w, h, d] = self.size();
+        if x >= w || y >= h || z >= d {
+            return None;
+        }
+        let i = (y as usize * d as usize + z as usize) * w as usize + x as usize;
+        self.data().get(i).map(|&voxel| Voxel::from(voxel))
+    }
+}
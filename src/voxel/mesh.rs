@@ -0,0 +1,237 @@
+use super::*;
+use std::collections::HashMap;
+
+// `M3D_UNDEF` narrowed to the configured index width.
+const UNDEF: Index = Index::MAX;
+
+// The slicing axis, followed by the two axes spanning a slice.
+const AXES: [[usize; 3]; 3] = [[0, 1, 2], [1, 2, 0], [2, 0, 1]];
+
+/// Triangles meshed from voxels, in the same layout as [`Obj::vertices`] and [`Obj::faces`]:
+/// normals are vertices too, and faces reference both by index.
+#[derive(Debug, Clone, Default)]
+pub struct VoxelMesh {
+    pub vertices: Vec<Vertex>,
+    pub faces: Vec<Face>,
+}
+
+impl Obj {
+    /// Meshes every voxel block, one unit per voxel, in grid coordinates.
+    ///
+    /// Faces between two voxels are culled, also across blocks, and neighbouring faces of the same
+    /// voxel type are merged into larger quads. Vertices take the voxel type's color and skin,
+    /// faces its material. Where blocks overlap the later one wins, unless its voxel is undefined.
+    pub fn mesh_voxels(&self) -> VoxelMesh {
+        let blocks = self.voxels();
+        let mut mesher = Mesher {
+            types: self.voxel_types(),
+            blocks,
+            touching: touching(blocks),
+            mesh: VoxelMesh::default(),
+            vertices: HashMap::new(),
+            normals: [None; 6],
+        };
+        for block in 0..mesher.blocks.len() {
+            for axes in AXES {
+                for dir in [-1, 1] {
+                    mesher.mesh_block(block, axes, dir);
+                }
+            }
+        }
+        mesher.mesh
+    }
+}
+
+// Bounds of every block, the far corner exclusive.
+fn bounds(block: &VoxelBlock) -> [[i64; 3]; 2] {
+    let min = block.position().map(|c| c as i64);
+    let size = block.size();
+    [min, std::array::from_fn(|k| min[k] + size[k] as i64)]
+}
+
+// For every block the blocks overlapping or next to it, itself included, the last one first.
+// Sorting along x keeps this from comparing every pair of blocks that are far apart.
+fn touching(blocks: &[VoxelBlock]) -> Vec<Vec<usize>> {
+    let bounds: Vec<_> = blocks.iter().map(bounds).collect();
+    let mut order: Vec<usize> = (0..blocks.len()).collect();
+    order.sort_by_key(|&i| bounds[i][0][0]);
+
+    let mut touching: Vec<Vec<usize>> = (0..blocks.len()).map(|i| vec![i]).collect();
+    for (n, &i) in order.iter().enumerate() {
+        let [min, max] = bounds[i];
+        for &j in &order[n + 1..] {
+            let [other_min, other_max] = bounds[j];
+            if other_min[0] > max[0] {
+                break;
+            }
+            if (0..3).all(|k| other_min[k] <= max[k] && min[k] <= other_max[k]) {
+                touching[i].push(j);
+                touching[j].push(i);
+            }
+        }
+    }
+    for blocks in &mut touching {
+        blocks.sort_unstable_by(|a, b| b.cmp(a));
+    }
+    touching
+}
+
+struct Mesher<'a> {
+    types: &'a [VoxelType],
+    blocks: &'a [VoxelBlock],
+    touching: Vec<Vec<usize>>,
+    mesh: VoxelMesh,
+    vertices: HashMap<([i64; 3], Index), Index>,
+    normals: [Option<Index>; 6],
+}
+
+impl Mesher<'_> {
+    // The block owning a grid position and the voxel type there, `None` where it is empty. Only
+    // the blocks touching `near` are looked at, which has to be within or next to it.
+    fn at(&self, near: usize, pos: [i64; 3]) -> Option<(usize, Index)> {
+        for &i in &self.touching[near] {
+            let block = &self.blocks[i];
+            let origin = block.position();
+            let local: [i64; 3] = std::array::from_fn(|k| pos[k] - origin[k] as i64);
+            let size = block.size();
+            if (0..3).any(|k| local[k] < 0 || local[k] >= size[k] as i64) {
+                continue;
+            }
+            match block.get(local[0] as _, local[1] as _, local[2] as _) {
+                Some(Voxel::Type(id)) if (id as usize) < self.types.len() => return Some((i, id)),
+                Some(Voxel::Undefined) => continue,
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    // Faces of one block pointing along `dir` on the first of `axes`, slice by slice.
+    fn mesh_block(&mut self, block: usize, [a, u, v]: [usize; 3], dir: i64) {
+        let origin = self.blocks[block].position().map(|c| c as i64);
+        let size = self.blocks[block].size().map(|c| c as usize);
+        let (w, h) = (size[u], size[v]);
+        let mut mask = vec![None; w * h];
+
+        for s in 0..size[a] {
+            for j in 0..h {
+                for i in 0..w {
+                    let mut pos = origin;
+                    pos[a] += s as i64;
+                    pos[u] += i as i64;
+                    pos[v] += j as i64;
+                    let mut next = pos;
+                    next[a] += dir;
+                    mask[j * w + i] = match self.at(block, pos) {
+                        Some((owner, id)) if owner == block && self.at(block, next).is_none() => {
+                            Some(id)
+                        }
+                        _ => None,
+                    };
+                }
+            }
+
+            for j in 0..h {
+                let mut i = 0;
+                while i < w {
+                    let id = match mask[j * w + i] {
+                        Some(id) => id,
+                        None => {
+                            i += 1;
+                            continue;
+                        }
+                    };
+                    let mut width = 1;
+                    while i + width < w && mask[j * w + i + width] == Some(id) {
+                        width += 1;
+                    }
+                    let mut height = 1;
+                    while j + height < h
+                        && (0..width).all(|k| mask[(j + height) * w + i + k] == Some(id))
+                    {
+                        height += 1;
+                    }
+                    for y in j..j + height {
+                        mask[y * w + i..y * w + i + width].fill(None);
+                    }
+
+                    let mut corner = origin;
+                    corner[a] += s as i64 + (dir > 0) as i64;
+                    corner[u] += i as i64;
+                    corner[v] += j as i64;
+                    self.quad(id, corner, [a, u, v], dir, [width as i64, height as i64]);
+                    i += width;
+                }
+            }
+        }
+    }
+
+    fn quad(
+        &mut self,
+        id: Index,
+        corner: [i64; 3],
+        [a, u, v]: [usize; 3],
+        dir: i64,
+        [w, h]: [i64; 2],
+    ) {
+        let mut corners = [corner; 4];
+        corners[1][u] += w;
+        corners[2][u] += w;
+        corners[2][v] += h;
+        corners[3][v] += h;
+        // `u` then `v` winds counterclockwise around the positive axis.
+        if dir < 0 {
+            corners.swap(1, 3);
+        }
+
+        let vertex = corners.map(|pos| self.vertex(pos, id));
+        let normal = self.normal(a, dir);
+        let materialid = self.types[id as usize].material();
+        for [b, c] in [[1, 2], [2, 3]] {
+            self.mesh.faces.push(Face {
+                materialid,
+                vertex: [vertex[0], vertex[b], vertex[c]],
+                normal: [normal; 3],
+                texcoord: [UNDEF; 3],
+            });
+        }
+    }
+
+    fn vertex(&mut self, pos: [i64; 3], id: Index) -> Index {
+        if let Some(&index) = self.vertices.get(&(pos, id)) {
+            return index;
+        }
+        let voxel_type = &self.types[id as usize];
+        let index = self.push(
+            [pos[0] as _, pos[1] as _, pos[2] as _],
+            voxel_type.color(),
+            voxel_type.skin(),
+        );
+        self.vertices.insert((pos, id), index);
+        index
+    }
+
+    fn normal(&mut self, axis: usize, dir: i64) -> Index {
+        let slot = axis * 2 + (dir > 0) as usize;
+        if let Some(index) = self.normals[slot] {
+            return index;
+        }
+        let mut normal = [0.0; 3];
+        normal[axis] = dir as Float;
+        let index = self.push(normal, Color::default(), UNDEF);
+        self.normals[slot] = Some(index);
+        index
+    }
+
+    fn push(&mut self, [x, y, z]: [Float; 3], color: Color, skinid: Index) -> Index {
+        self.mesh.vertices.push(Vertex {
+            x,
+            y,
+            z,
+            w: 1.0,
+            color: color.into(),
+            skinid,
+        });
+        (self.mesh.vertices.len() - 1) as _
+    }
+}
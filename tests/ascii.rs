@@ -0,0 +1,210 @@
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+mod common;
+
+use common::*;
+use model3d::*;
+
+const TRIANGLE: &str = "3dmodel 1
+triangle
+MIT
+model3d
+A single triangle.
+
+Vertex
+0 0 0 1
+1 0 0 1 #ff0000ff
+0 1 0 1
+
+Material white
+Kd #ffffffff
+
+Mesh
+use white
+0 1 2
+";
+
+// Laid out the way the SDK's exporter writes text: CRLF line ends, `%g` numbers, a full weight
+// as the bone id alone, `use` without a name for faces without a material, and a closing `End`.
+const SDK_WRITTEN: &str = "3dmodel 1\r
+quad\r
+MIT\r
+model3d\r
+A skinned quad.\r
+\r
+Textmap\r
+0 0\r
+1 0\r
+1 1\r
+0 1\r
+\r
+Vertex\r
+-0.5 -0.5 0 1 #ff0000ff 0\r
+0.5 -0.5 0 1 0\r
+0.5 0.5 0 1 0:0.5 1:0.5\r
+-0.5 0.5 0 1 1\r
+0 0 1 1\r
+0 0 0 1\r
+0 0.5 0 1\r
+\r
+Bones\r
+5 5 root\r
+/6 5 tip\r
+\r
+Material red\r
+Kd #ff0a0ac8\r
+Ns 32\r
+Pr 0.5\r
+\r
+Mesh\r
+use red\r
+0/0/4 1/1/4 2/2/4\r
+use\r
+0/0/4 2/2/4 3/3/4\r
+\r
+End\r
+";
+
+fn syntax_error(text: &str) -> SyntaxError {
+    ModelData::from_ascii(text).expect_err("refused")
+}
+
+#[test]
+fn parses_text() {
+    let data = ModelData::from_ascii(TRIANGLE).unwrap();
+    assert_eq!(data.name, "triangle");
+    assert_eq!(data.desc, "A single triangle.");
+    assert_eq!(data.vertices.len(), 3);
+    assert_eq!(data.vertices[1].color, 0xff0000ff);
+    assert_eq!(data.materials[0].name, "white");
+    assert_eq!(data.faces.len(), 1);
+    assert_eq!(data.faces[0].vertex, [0, 1, 2]);
+    assert_eq!(data.faces[0].materialid, 0);
+}
+
+#[test]
+fn loads_text() {
+    let model = load(TRIANGLE.as_bytes()).unwrap().model;
+    assert_eq!(model.name(), "triangle");
+    assert_eq!(model.faces().len(), 1);
+    assert_eq!(model.materials()[0].diffuse(), Some(Color(0xffffffff)));
+}
+
+#[test]
+fn loads_sdk_written_text() {
+    let report = load(SDK_WRITTEN.as_bytes()).unwrap();
+    assert!(report.warnings.is_empty());
+    let model = report.model;
+    assert_eq!((model.name(), model.desc()), ("quad", "A skinned quad."));
+    assert_eq!(model.texture_maps().len(), 4);
+
+    let vertices = model.vertices();
+    assert_eq!(vertices[0].color, 0xff0000ff);
+    let skin = |v: usize| model.skins()[vertices[v].skinid as usize];
+    assert_eq!((skin(0).boneid[0], skin(0).weight[0]), (0, 1.0));
+    assert_eq!((skin(3).boneid[0], skin(3).weight[0]), (1, 1.0));
+    assert_eq!(skin(2).boneid[..2], [0, 1]);
+    assert_close(
+        &[[skin(2).weight[0], skin(2).weight[1], 0.0]],
+        &[[0.5, 0.5, 0.0]],
+        1e-6,
+    );
+
+    let bones = model.bones();
+    assert_eq!(bones.len(), 2);
+    assert_eq!((bones[1].name(), bones[1].parent()), ("tip", 0));
+
+    let material = &model.materials()[0];
+    assert_eq!(material.diffuse(), Some(Color(0xff0a0ac8)));
+    assert_eq!(material.specular_exponent(), Some(32.0));
+    assert_eq!(material.roughness(), Some(0.5));
+
+    let faces = model.faces();
+    assert_eq!(faces.len(), 2);
+    assert_eq!(faces[0].materialid, 0);
+    assert_eq!(faces[1].materialid, Index::MAX);
+    assert_eq!(faces[1].vertex, [0, 2, 3]);
+    assert_eq!(faces[1].texcoord, [0, 2, 3]);
+    assert_eq!(faces[1].normal, [4; 3]);
+}
+
+#[test]
+fn text_roundtrip() {
+    let options = SaveOptions::new().container(Container::Ascii);
+    let text = sample().save(options).unwrap();
+    let model = load(&text).unwrap().model;
+    let expected = Model::from_data(&sample()).unwrap();
+    assert_close(&corners(&model), &corners(&expected), 1e-6);
+    assert_eq!(model.bones().len(), 2);
+    assert_eq!(model.actions()[0].frames().len(), 2);
+    assert_eq!(model.labels()[0].text(), "first corner");
+    assert_eq!(model.voxels()[0].data(), &sample().voxels[0].data[..]);
+
+    let data = ModelData::from_ascii(std::str::from_utf8(&text).unwrap()).unwrap();
+    assert_eq!(data.save(options).unwrap(), text);
+}
+
+#[test]
+fn binary_only_options_are_refused() {
+    let ascii = SaveOptions::new().container(Container::Ascii);
+    for options in [
+        ascii.compression(Compression::None),
+        ascii.quantization(QuantizeQuality::Int16),
+        ascii.inline_textures(true),
+        ascii.extra_chunks(true),
+    ] {
+        let err = sample().save(options).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidOptions);
+    }
+}
+
+#[test]
+fn error_positions() {
+    let err = syntax_error("3dmodle\n");
+    assert_eq!((err.line, err.column), (1, 1));
+
+    let text = TRIANGLE.replace("1 0 0 1 #ff0000ff", "1 zero 0 1");
+    let err = syntax_error(&text);
+    assert_eq!((err.line, err.column), (9, 3));
+    assert_eq!(err.message, "expected a number");
+
+    let text = TRIANGLE.replace("0 1 2", "0 1");
+    assert_eq!(syntax_error(&text).line, 17);
+}
+
+#[test]
+fn load_reports_positions() {
+    let text = TRIANGLE.replace("Kd #ffffffff", "Kd white");
+    let err = load(text.as_bytes()).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+    let syntax = err.syntax().unwrap();
+    assert_eq!((syntax.line, syntax.column), (13, 4));
+    assert_eq!(
+        err.to_string(),
+        "syntax error at line 13, column 4: expected a #hex color"
+    );
+}
+
+#[test]
+fn unknown_materials_and_sections_are_warnings() {
+    // Like the binary reader, the face is kept without a material.
+    let text = TRIANGLE.replace("use white", "use black");
+    let report = load(text.as_bytes()).unwrap();
+    assert_eq!(report.model.faces()[0].materialid, Index::MAX);
+    let warning = &report.warnings[0].0;
+    assert_eq!(warning.kind(), ErrorKind::Material);
+    let syntax = warning.syntax().unwrap();
+    assert_eq!((syntax.line, syntax.column), (16, 5));
+    assert_eq!(syntax.message, "unknown material");
+
+    let text = TRIANGLE.replace("Mesh", "Meshes");
+    let report = load(text.as_bytes()).unwrap();
+    assert!(report.model.faces().is_empty());
+    let warning = &report.warnings[0].0;
+    assert_eq!(warning.kind(), ErrorKind::Unimplemented);
+    assert_eq!(warning.chunk(), Some("Meshes"));
+    assert_eq!(warning.syntax().unwrap().line, 15);
+
+    let strict = Model::load(text.as_bytes(), LoadOptions::new().strict(true));
+    assert!(matches!(strict, Err(err) if err.kind() == ErrorKind::Unimplemented));
+}
@@ -0,0 +1,58 @@
+//! Packed colors and the sRGB transfer function.
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+use model3d::*;
+
+fn gray(c: u8) -> Color {
+    Color::from_rgba8([c, c, c, 255])
+}
+
+fn assert_near(a: f32, b: f32) {
+    assert!((a - b).abs() <= 1e-5, "{} != {}", a, b);
+}
+
+#[test]
+fn srgb_to_linear() {
+    assert_eq!(gray(0).to_linear(), [0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(gray(255).to_linear(), [1.0; 4]);
+    // 0.5 is stored as 128.
+    assert_eq!(Color::from_srgb([0.5; 4]).to_rgba8(), [128; 4]);
+    assert_near(gray(128).to_linear()[0], 0.215861);
+    // Either side of the 0.04045 knee, linear below and the power curve above.
+    assert_near(gray(10).to_linear()[0], 10.0 / 255.0 / 12.92);
+    assert_near(gray(11).to_linear()[0], 0.003347);
+    // Alpha is linear already.
+    assert_near(
+        Color::from_rgba8([0, 0, 0, 128]).to_linear()[3],
+        128.0 / 255.0,
+    );
+}
+
+#[test]
+fn linear_to_srgb() {
+    assert_eq!(
+        Color::from_linear([0.0, 0.5, 1.0, 0.5]).to_rgba8(),
+        [0, 188, 255, 128]
+    );
+    assert_eq!(Color::from_linear([0.0031308; 4]).to_rgba8()[0], 10);
+    // Out of range values are clamped.
+    assert_eq!(
+        Color::from_linear([-1.0, 2.0, 0.0, 1.0]).to_rgba8(),
+        [0, 255, 0, 255]
+    );
+}
+
+#[test]
+fn packed_rgba_round_trip() {
+    let color = Color::from_rgba8([0x11, 0x22, 0x33, 0x44]);
+    // Red in the low byte, like Model3D.
+    assert_eq!(u32::from(color), 0x44332211);
+    assert_eq!(Color::from(0x44332211), color);
+    assert_eq!(color.to_rgba8(), [0x11, 0x22, 0x33, 0x44]);
+
+    for c in 0..=255 {
+        let color = Color::from_rgba8([c, 255 - c, c / 2, c]);
+        assert_eq!(Color::from_srgb(color.to_srgb()), color);
+        assert_eq!(Color::from_linear(color.to_linear()), color);
+    }
+}
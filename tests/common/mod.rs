@@ -0,0 +1,121 @@
+#![allow(dead_code)]
+
+use model3d::*;
+use std::path::PathBuf;
+
+/// A textured, skinned and animated quad, with a label and a voxel block, inside the unit cube
+/// so that saving doesn't rescale it.
+pub fn sample() -> ModelData {
+    let mut b = ModelBuilder::new();
+    b.name("sample").author("model3d").license("MIT");
+
+    let corners = [
+        [-0.5, -0.5, 0.0],
+        [0.5, -0.5, 0.0],
+        [0.5, 0.5, 0.0],
+        [-0.5, 0.5, 0.0],
+    ];
+    let v = corners.map(|c| b.add_vertex(c));
+    let n = b.add_normal([0.0, 0.0, 1.0]);
+    let uv = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]].map(|[u, v]| b.add_texcoord(u, v));
+    b.set_vertex_color(v[0], Color::from_rgba8([255, 0, 0, 255]))
+        .unwrap();
+
+    let material = b.add_material("red").unwrap();
+    b.set_material_property(
+        material,
+        PropertyKind::Diffuse,
+        PropertyValue::Color(Color::from_rgba8([200, 10, 10, 255])),
+    )
+    .unwrap();
+    b.set_material_property(material, PropertyKind::Roughness, PropertyValue::Float(0.5))
+        .unwrap();
+
+    for [i, j, k] in [[0, 1, 2], [0, 2, 3]] {
+        b.add_face(
+            Some(material),
+            [v[i], v[j], v[k]],
+            Some([uv[i], uv[j], uv[k]]),
+            Some([n; 3]),
+        )
+        .unwrap();
+    }
+
+    let root = b
+        .add_bone("root", None, [0.0; 3], [0.0, 0.0, 0.0, 1.0])
+        .unwrap();
+    let tip = b
+        .add_bone("tip", Some(root), [0.0, 0.5, 0.0], [0.0, 0.0, 0.0, 1.0])
+        .unwrap();
+    b.add_skin_weight(v[2], tip, 1.0).unwrap();
+    b.add_skin_weight(v[3], tip, 1.0).unwrap();
+
+    let action = b.add_action("wave", 1000);
+    for msec in [0, 500] {
+        let frame = b.add_frame(action, msec).unwrap();
+        b.add_transform(action, frame, tip, [0.0, 0.5, 0.0], [0.0, 0.0, 0.0, 1.0])
+            .unwrap();
+    }
+    b.add_label(
+        "corner",
+        "en",
+        "first corner",
+        v[0],
+        Color::from_rgba8([0, 0, 0, 255]),
+    )
+    .unwrap();
+
+    let mut data = b.into_data();
+    data.voxel_types.push(VoxelTypeData {
+        name: "stone".to_string(),
+        rotation: 0,
+        shape: 0,
+        material_id: Index::MAX,
+        color: Color::from_rgba8([128, 128, 128, 255]).into(),
+        skin_id: Index::MAX,
+        items: Vec::new(),
+    });
+    data.voxels.push(VoxelBlockData {
+        name: "floor".to_string(),
+        width: 2,
+        height: 1,
+        depth: 2,
+        data: vec![0, 0, 0, 0],
+        ..Default::default()
+    });
+    data
+}
+
+pub fn load(data: &[u8]) -> Result<LoadReport, Error> {
+    Model::load(data, LoadOptions::new())
+}
+
+/// Positions of every face corner, in face order.
+pub fn corners(model: &Obj) -> Vec<[f32; 3]> {
+    model
+        .faces()
+        .iter()
+        .flat_map(|f| f.vertex)
+        .map(|i| {
+            let v = model.vertices()[i as usize];
+            [v.x, v.y, v.z]
+        })
+        .collect()
+}
+
+pub fn assert_close(a: &[[f32; 3]], b: &[[f32; 3]], tolerance: f32) {
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(b) {
+        for k in 0..3 {
+            assert!((a[k] - b[k]).abs() <= tolerance, "{:?} != {:?}", a, b);
+        }
+    }
+}
+
+/// An empty directory of its own under the system temp dir.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("model3d-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
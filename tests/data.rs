@@ -0,0 +1,115 @@
+//! `ModelData` coming from safe code, which `Model::from_data` has to check before the C structs
+//! are laid out from it.
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+mod common;
+
+use common::*;
+use model3d::*;
+
+// `m3dc_polygon`, a vertex count followed by that many vertices.
+const POLYGON: u16 = 31;
+
+fn with_command(kind: u16, args: Vec<u32>) -> ModelData {
+    let mut data = sample();
+    data.shapes.push(ShapeData {
+        name: "outline".to_string(),
+        group: Index::MAX,
+        commands: vec![ShapeCommandData { kind, args }],
+    });
+    data
+}
+
+#[test]
+fn shape_commands_survive() {
+    let data = with_command(POLYGON, vec![3, 0, 1, 2]);
+    let model = Model::from_data(&data).unwrap();
+    assert_eq!(model.to_data().shapes, data.shapes);
+}
+
+#[test]
+fn shape_commands_need_their_arguments() {
+    for args in [vec![], vec![3, 0], vec![0], vec![1, 0, 1]] {
+        let err = Model::from_data(&with_command(POLYGON, args.clone())).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Shape, "{:?}", args);
+    }
+    let err = Model::from_data(&with_command(1000, vec![0])).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownCmd);
+}
+
+#[test]
+fn voxel_data_has_to_fill_the_block() {
+    let mut data = sample();
+    data.voxels[0].data.pop();
+    let err = Model::from_data(&data).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownkVoxex);
+
+    let mut data = sample();
+    data.voxels[0].width = u32::MAX;
+    data.voxels[0].height = u32::MAX;
+    assert!(Model::from_data(&data).is_err());
+}
+
+#[test]
+fn extra_chunks_need_their_length() {
+    let mut chunk = b"XTRA\x0a\0\0\0hi".to_vec();
+    let mut data = sample();
+    data.extra.push(chunk.clone());
+    let model = Model::from_data(&data).unwrap();
+    assert_eq!(model.to_data().extra, data.extra);
+
+    chunk.push(b'!');
+    for extra in [chunk, b"XTRA".to_vec(), Vec::new()] {
+        let mut data = sample();
+        data.extra.push(extra);
+        let err = Model::from_data(&data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::BadFile);
+    }
+}
+
+#[test]
+fn builder_textures_are_saved() {
+    let mut b = ModelBuilder::new();
+    let v = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]].map(|c| b.add_vertex(c));
+    let pixels = vec![0, 64, 128, 255];
+    let texture = b
+        .add_texture(TextureData {
+            name: "gray".to_string(),
+            width: 2,
+            height: 2,
+            format: TextureFormat::Grayscale,
+            pixels: pixels.clone(),
+        })
+        .unwrap();
+    let material = b.add_material("painted").unwrap();
+    b.set_material_property(
+        material,
+        PropertyKind::Diffuse,
+        PropertyValue::Texture(texture),
+    )
+    .unwrap();
+    b.add_face(Some(material), v, None, None).unwrap();
+
+    let model = load(&b.save(SaveOptions::new()).unwrap()).unwrap().model;
+    assert_eq!(model.textures()[0].name(), "gray");
+    assert_eq!(model.textures()[0].data(), pixels);
+    let ascii = SaveOptions::new().container(Container::Ascii);
+    assert_eq!(b.save(ascii).unwrap_err().kind(), ErrorKind::InvalidOptions);
+}
+
+#[test]
+fn builder_textures_need_a_name_and_their_pixels() {
+    let mut b = ModelBuilder::new();
+    let texture = |name: &str, pixels: Vec<u8>| TextureData {
+        name: name.to_string(),
+        width: 2,
+        height: 1,
+        format: TextureFormat::RGB,
+        pixels,
+    };
+    assert!(b.add_texture(texture("named", Vec::new())).is_ok());
+    for bad in [texture("", Vec::new()), texture("short", vec![0; 5])] {
+        let err = b.add_texture(bad).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownImg);
+    }
+}
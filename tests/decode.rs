@@ -0,0 +1,275 @@
+//! The native decoder, which has to refuse damaged files instead of reading past them.
+#![cfg(feature = "pure-rust")]
+
+mod common;
+
+use common::*;
+use model3d::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+fn uncompressed() -> Vec<u8> {
+    let options = SaveOptions::new().compression(Compression::None);
+    sample().save(options).unwrap()
+}
+
+// Offset and length of the first chunk with `magic`.
+fn find_chunk(file: &[u8], magic: &[u8; 4]) -> (usize, usize) {
+    let mut offset = 8;
+    while offset + 8 <= file.len() {
+        let len = u32::from_le_bytes(file[offset + 4..offset + 8].try_into().unwrap()) as usize;
+        if &file[offset..offset + 4] == magic {
+            return (offset, len);
+        }
+        offset += len.max(8);
+    }
+    panic!("no {} chunk", String::from_utf8_lossy(magic));
+}
+
+fn set_len(file: &mut [u8], offset: usize, len: usize) {
+    file[offset + 4..offset + 8].copy_from_slice(&(len as u32).to_le_bytes());
+}
+
+#[test]
+fn decodes_what_was_built() {
+    let model = load(&uncompressed()).unwrap().model;
+    let expected = Model::from_data(&sample()).unwrap();
+    assert_close(&corners(&model), &corners(&expected), 0.0);
+    assert_eq!(model.bones().len(), 2);
+    assert!(!model.skins().is_empty());
+}
+
+#[test]
+fn rejects_what_is_not_a_model() {
+    for data in [&b""[..], b"3DMO", b"3DMO\x08\0\0\0", b"not a model"] {
+        let err = load(data).err().expect("refused");
+        assert_eq!(err.kind(), ErrorKind::BadFile, "{:?}", data);
+    }
+    // A header cut short.
+    assert!(load(b"3DMO\x0c\0\0\0HEAD").is_err());
+}
+
+#[test]
+fn truncated_files_never_panic() {
+    let file = uncompressed();
+    for len in 0..file.len() {
+        let mut truncated = file[..len].to_vec();
+        // Keep the file header consistent, so that the chunks themselves come up short.
+        if len >= 8 {
+            set_len(&mut truncated, 0, len);
+        }
+        let _ = load(&truncated);
+    }
+}
+
+#[test]
+fn truncated_zlib_stream_is_refused() {
+    let file = sample().save(SaveOptions::new()).unwrap();
+    let mut truncated = file[..file.len() - 16].to_vec();
+    let len = truncated.len();
+    set_len(&mut truncated, 0, len);
+    assert_eq!(load(&truncated).err().unwrap().kind(), ErrorKind::BadFile);
+}
+
+#[test]
+fn chunk_longer_than_the_file_is_refused() {
+    let mut file = uncompressed();
+    let (offset, _) = find_chunk(&file, b"MESH");
+    let len = file.len();
+    set_len(&mut file, offset, len);
+    assert_eq!(load(&file).err().unwrap().kind(), ErrorKind::BadFile);
+}
+
+#[test]
+fn chunk_cut_short_is_reported() {
+    // The mesh chunk ends in the middle of its last face.
+    let mut file = uncompressed();
+    let (offset, len) = find_chunk(&file, b"MESH");
+    file.drain(offset + len - 2..offset + len);
+    set_len(&mut file, offset, len - 2);
+    let total = file.len();
+    set_len(&mut file, 0, total);
+
+    match load(&file) {
+        Ok(report) => {
+            assert_eq!(report.model.faces().len(), 1);
+            assert_eq!(report.warnings[0].0.chunk(), Some("MESH"));
+        }
+        Err(err) => assert_eq!(err.chunk(), Some("MESH")),
+    }
+    let strict = Model::load(&file, LoadOptions::new().strict(true));
+    assert!(strict.is_err());
+}
+
+#[test]
+fn skin_count_is_bounded_by_the_chunk() {
+    // One bone per vertex and no bone indices, so every skin would be read from zero bytes.
+    let types: u32 = (3 << 10) | (2 << 14);
+    let mut head = b"HEAD\x14\0\0\0".to_vec();
+    head.extend(1.0f32.to_le_bytes());
+    head.extend(types.to_le_bytes());
+    head.extend([0; 4]);
+    let mut file = b"3DMO\0\0\0\0".to_vec();
+    file.extend(head);
+    file.extend(b"BONE\x0c\0\0\0");
+    file.extend(0xFFFF_FFF0u32.to_le_bytes());
+    file.extend(b"OMD3");
+    let total = file.len();
+    set_len(&mut file, 0, total);
+
+    let report = load(&file).unwrap();
+    assert!(report.model.skins().is_empty());
+    assert_eq!(report.warnings[0].0.kind(), ErrorKind::Truncating);
+    assert_eq!(report.warnings[0].0.chunk(), Some("BONE"));
+}
+
+#[test]
+fn out_of_range_indices_are_refused() {
+    // Point the first face at a vertex which does not exist.
+    let mut data = sample();
+    data.faces[0].vertex[0] = data.vertices.len() as _;
+    let saved = data.save(SaveOptions::new().compression(Compression::None));
+    match saved {
+        Ok(file) => assert!(load(&file).is_err()),
+        Err(err) => assert_eq!(err.kind(), ErrorKind::Vertices),
+    }
+}
+
+// Two materials mapping the texture `name`, which the file leaves to be resolved.
+fn mapped_texture(name: &str) -> Vec<u8> {
+    let mut b = ModelBuilder::new();
+    let v = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]].map(|c| b.add_vertex(c));
+    let texture = b
+        .add_texture(TextureData {
+            name: name.to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+    for name in ["front", "back"] {
+        let material = b.add_material(name).unwrap();
+        b.set_material_property(
+            material,
+            PropertyKind::Diffuse,
+            PropertyValue::Texture(texture),
+        )
+        .unwrap();
+        b.add_face(Some(material), v, None, None).unwrap();
+    }
+    b.save(SaveOptions::new()).unwrap()
+}
+
+#[test]
+fn missing_textures_are_reported() {
+    let file = mapped_texture("nowhere");
+    let report = load(&file).unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].0.kind(), ErrorKind::UnknownImg);
+    assert_eq!(report.warnings[0].0.asset(), Some("nowhere"));
+    assert!(report.model.textures().is_empty());
+    assert!(report.model.materials()[0].props().is_empty());
+    let strict = Model::load(&file, LoadOptions::new().strict(true));
+    assert!(matches!(strict, Err(err) if err.kind() == ErrorKind::UnknownImg));
+}
+
+#[test]
+fn texture_scripts_are_run() {
+    let file = mapped_texture("noise");
+    let scripts = HashMap::from([("noise".to_string(), b"script".to_vec())]);
+    let calls = Cell::new(0);
+    let generate = |name: &str, script: &[u8]| {
+        calls.set(calls.get() + 1);
+        assert_eq!((name, script), ("noise", &b"script"[..]));
+        Some(GeneratedTexture {
+            width: 1,
+            height: 1,
+            format: TextureFormat::RGBA,
+            pixels: vec![1, 2, 3, 4],
+        })
+    };
+    let options = LoadOptions::new().resolver(&scripts);
+    let report = Model::load(&file, options.texture_script(&generate)).unwrap();
+    assert_eq!(calls.get(), 1);
+    assert!(report.warnings.is_empty());
+    let texture = &report.model.textures()[0];
+    assert_eq!(
+        (texture.name(), texture.width(), texture.height()),
+        ("noise", 1, 1)
+    );
+    assert_eq!(texture.data(), [1, 2, 3, 4]);
+    assert_eq!(
+        report.model.materials()[1].map(PropertyKind::Diffuse),
+        Some(0)
+    );
+
+    // A script that produces nothing leaves the texture empty.
+    let fail = |_: &str, _: &[u8]| None;
+    let options = LoadOptions::new().resolver(&scripts);
+    let report = Model::load(&file, options.texture_script(&fail)).unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].0.kind(), ErrorKind::UnknownImg);
+    assert_eq!(report.warnings[0].0.asset(), Some("noise"));
+    assert!(report.model.textures()[0].data().is_empty());
+    let options = LoadOptions::new().resolver(&scripts).strict(true);
+    let strict = Model::load(&file, options.texture_script(&fail));
+    assert!(matches!(strict, Err(err) if err.kind() == ErrorKind::UnknownImg));
+}
+
+const SURFACE: &str = "3dmodel 1
+surface
+MIT
+model3d
+A generated triangle.
+
+Procedural
+surface
+";
+
+fn triangle(_: &str, _: &[u8], model: &mut ProceduralModel) -> Result<(), Error> {
+    let red = PropertyValue::Color(Color::from_rgba8([255, 0, 0, 255]));
+    let material = model.add_material("generated", &[(PropertyKind::Diffuse, red)])?;
+    let mut vertex = |x, y| {
+        model.add_vertex(Vertex {
+            x,
+            y,
+            z: 0.0,
+            w: 1.0,
+            color: 0,
+            skinid: Index::MAX,
+        })
+    };
+    let vertex = [vertex(0.0, 0.0)?, vertex(1.0, 0.0)?, vertex(0.0, 1.0)?];
+    model.add_face(Face {
+        materialid: material,
+        vertex,
+        normal: [Index::MAX; 3],
+        texcoord: [Index::MAX; 3],
+    })?;
+    Ok(())
+}
+
+fn refuse(_: &str, _: &[u8], _: &mut ProceduralModel) -> Result<(), Error> {
+    Err(ErrorKind::Shape.into())
+}
+
+#[test]
+fn procedural_scripts_add_to_the_model() {
+    let scripts = HashMap::from([("surface".to_string(), b"script".to_vec())]);
+    let options = LoadOptions::new().resolver(&scripts);
+    let report = Model::load(SURFACE.as_bytes(), options.procedural(&triangle)).unwrap();
+    assert!(report.warnings.is_empty());
+    assert_eq!(report.model.faces().len(), 1);
+    let material = &report.model.materials()[0];
+    assert_eq!(
+        material.diffuse(),
+        Some(Color::from_rgba8([255, 0, 0, 255]))
+    );
+
+    // Without a handler the script is not supported, otherwise the handler's error is kept.
+    let options = LoadOptions::new().resolver(&scripts);
+    let report = Model::load(SURFACE.as_bytes(), options).unwrap();
+    assert_eq!(report.warnings[0].0.kind(), ErrorKind::Unimplemented);
+    assert_eq!(report.warnings[0].0.asset(), Some("surface"));
+    let options = LoadOptions::new().resolver(&scripts);
+    let report = Model::load(SURFACE.as_bytes(), options.procedural(&refuse)).unwrap();
+    assert_eq!(report.warnings[0].0.kind(), ErrorKind::Shape);
+}
@@ -0,0 +1,104 @@
+//! Resource limits, checked against what the file claims before it is decoded.
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+mod common;
+
+use common::*;
+use model3d::*;
+
+fn exceeded(file: &[u8], limits: Limits) -> Error {
+    match Model::load(file, LoadOptions::new().limits(limits)) {
+        Ok(_) => panic!("loaded past the limits"),
+        Err(err) => err,
+    }
+}
+
+#[test]
+fn sample_fits_the_defaults() {
+    let file = sample().save(SaveOptions::new()).unwrap();
+    assert!(Model::load(&file, LoadOptions::new().limits(Limits::default())).is_ok());
+}
+
+#[test]
+fn counts_are_checked_per_chunk() {
+    let file = sample().save(SaveOptions::new()).unwrap();
+    let cases = [
+        (
+            Limits {
+                max_skins: 0,
+                ..Limits::unlimited()
+            },
+            "skins",
+            "BONE",
+        ),
+        (
+            Limits {
+                max_transforms: 1,
+                ..Limits::unlimited()
+            },
+            "transforms",
+            "ACTN",
+        ),
+        (
+            Limits {
+                max_voxels: 3,
+                ..Limits::unlimited()
+            },
+            "voxels",
+            "VOXD",
+        ),
+        (
+            Limits {
+                max_frames: 1,
+                ..Limits::unlimited()
+            },
+            "frames",
+            "ACTN",
+        ),
+    ];
+    for (limits, limit, chunk) in cases {
+        let err = exceeded(&file, limits);
+        assert_eq!(err.kind(), ErrorKind::LimitExceeded);
+        assert_eq!(err.limit(), Some(limit));
+        assert_eq!(err.chunk(), Some(chunk));
+    }
+}
+
+#[test]
+fn voxels_count_every_block() {
+    // Two blocks of 4 voxels, each within the limit on its own.
+    let mut data = sample();
+    let mut block = data.voxels[0].clone();
+    block.name = "ceiling".to_string();
+    block.y = 1;
+    data.voxels.push(block);
+    let file = data.save(SaveOptions::new()).unwrap();
+
+    let limits = Limits {
+        max_voxels: 4,
+        ..Limits::unlimited()
+    };
+    assert_eq!(exceeded(&file, limits).limit(), Some("voxels"));
+    let limits = Limits {
+        max_voxels: 8,
+        ..Limits::unlimited()
+    };
+    assert!(Model::load(&file, LoadOptions::new().limits(limits)).is_ok());
+}
+
+#[test]
+fn compressed_models_load_once_inflated() {
+    // The preview sits in front of the compressed body and has to survive.
+    let mut data = sample();
+    data.preview = b"\x89PNG not really".to_vec();
+    for compression in [Compression::None, Compression::Zlib] {
+        let file = data
+            .save(SaveOptions::new().compression(compression))
+            .unwrap();
+        let model = load(&file).unwrap().model;
+        let expected = Model::from_data(&sample()).unwrap();
+        assert_close(&corners(&model), &corners(&expected), 1.0 / 127.0);
+        assert_eq!(model.voxels().len(), 1);
+        assert_eq!(model.to_data().preview, data.preview);
+    }
+}
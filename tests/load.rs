@@ -0,0 +1,138 @@
+//! What `LoadOptions` change, and the sources a model is read from.
+#![cfg(feature = "pure-rust")]
+
+mod common;
+
+use common::*;
+use model3d::*;
+use std::io::Cursor;
+
+const TRIANGLE: &str = "3dmodel 1
+triangle
+MIT
+model3d
+A triangle without normals.
+
+Vertex
+0 0 0 1
+1 0 0 1
+0 1 0 1
+
+Mesh
+use red
+0 1 2
+";
+
+fn red() -> Color {
+    Color::from_rgba8([255, 0, 0, 255])
+}
+
+// A model mapping the texture "wood", with its pixels inlined or left to be resolved.
+fn wooden(inline: bool) -> Vec<u8> {
+    let mut b = ModelBuilder::new();
+    let v = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]].map(|c| b.add_vertex(c));
+    let (width, height, format) = (2, 1, TextureFormat::RGB);
+    let pixels = if inline {
+        vec![10, 20, 30, 40, 50, 60]
+    } else {
+        Vec::new()
+    };
+    let texture = b
+        .add_texture(TextureData {
+            name: "wood".to_string(),
+            width,
+            height,
+            format,
+            pixels,
+        })
+        .unwrap();
+    let material = b.add_material("wood").unwrap();
+    b.set_material_property(
+        material,
+        PropertyKind::Diffuse,
+        PropertyValue::Texture(texture),
+    )
+    .unwrap();
+    b.add_face(Some(material), v, None, None).unwrap();
+    b.save(SaveOptions::new()).unwrap()
+}
+
+#[test]
+fn normals_are_generated_unless_disabled() {
+    let options = LoadOptions::new().generate_normals(true);
+    let model = Model::load(TRIANGLE.as_bytes(), options).unwrap().model;
+    let normal = model.faces()[0].normal;
+    assert!(normal
+        .iter()
+        .all(|&n| (n as usize) < model.vertices().len()));
+    let n = model.vertices()[normal[0] as usize];
+    assert_close(&[[n.x, n.y, n.z]], &[[0.0, 0.0, 1.0]], 1e-6);
+
+    let options = LoadOptions::new().generate_normals(false);
+    let model = Model::load(TRIANGLE.as_bytes(), options).unwrap().model;
+    assert_eq!(model.faces()[0].normal, [Index::MAX; 3]);
+    assert_eq!(model.vertices().len(), 3);
+}
+
+#[test]
+fn inlined_assets_can_be_dropped() {
+    let file = wooden(true);
+    let model = load(&file).unwrap().model;
+    assert_eq!(model.inlined_textures()[0].name(), "wood");
+
+    let options = LoadOptions::new().ignore_inlined_assets(true);
+    let model = Model::load(&file, options).unwrap().model;
+    assert!(model.inlined_textures().is_empty());
+    assert_eq!(model.textures()[0].data(), [10, 20, 30, 40, 50, 60]);
+}
+
+#[test]
+fn materials_come_from_the_library() {
+    let mut b = ModelBuilder::new();
+    let material = b.add_material("red").unwrap();
+    b.set_material_property(material, PropertyKind::Diffuse, PropertyValue::Color(red()))
+        .unwrap();
+    let library = b.build().unwrap();
+
+    let report = Model::load(TRIANGLE.as_bytes(), LoadOptions::new().mtllib(library)).unwrap();
+    assert!(report.warnings.is_empty());
+    let model = report.model;
+    let material = &model.materials()[model.faces()[0].materialid as usize];
+    assert_eq!((material.name(), material.diffuse()), ("red", Some(red())));
+
+    // Without it, the face has no material.
+    let report = load(TRIANGLE.as_bytes()).unwrap();
+    assert_eq!(report.warnings[0].0.kind(), ErrorKind::Material);
+    assert_eq!(report.model.faces()[0].materialid, Index::MAX);
+}
+
+#[test]
+fn files_resolve_their_assets_next_to_them() {
+    let dir = temp_dir("load-path");
+    // The PNG the builder inlines for the same pixels.
+    let inlined = load(&wooden(true)).unwrap().model;
+    std::fs::write(dir.join("wood.png"), inlined.inlined_textures()[0].data()).unwrap();
+    let path = dir.join("wooden.m3d");
+    std::fs::write(&path, wooden(false)).unwrap();
+
+    let report = Model::load(&path, LoadOptions::new()).unwrap();
+    assert!(report.warnings.is_empty());
+    assert_eq!(report.model.textures()[0].data(), [10, 20, 30, 40, 50, 60]);
+
+    let missing = dir.join("missing.m3d");
+    let err = Model::load(&missing, LoadOptions::new()).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::FileNotFound);
+    assert_eq!(err.path(), Some(missing.as_path()));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn readers_are_read_to_the_end() {
+    let file = wooden(true);
+    let mut reader = Cursor::new(&file);
+    let model = Model::load(&mut reader, LoadOptions::new()).unwrap().model;
+    assert_eq!(reader.position(), file.len() as u64);
+    assert_eq!(model.faces().len(), 1);
+    assert_eq!(model.textures()[0].name(), "wood");
+}
@@ -0,0 +1,66 @@
+//! Reading material properties back by type.
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+use model3d::*;
+
+#[test]
+fn getters_only_return_their_type() {
+    let mut b = ModelBuilder::new();
+    let texture = b
+        .add_texture(TextureData {
+            name: "shine".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+    let material = b.add_material("polished").unwrap();
+    let red = Color::from_rgba8([255, 0, 0, 255]);
+    for (kind, value) in [
+        (PropertyKind::Diffuse, PropertyValue::Color(red)),
+        (PropertyKind::Roughness, PropertyValue::Float(0.25)),
+        (PropertyKind::Specular, PropertyValue::Texture(texture)),
+        (PropertyKind::IlluminationModel, PropertyValue::Int(2)),
+    ] {
+        b.set_material_property(material, kind, value).unwrap();
+    }
+    let model = b.build().unwrap();
+    let material = &model.materials()[0];
+
+    assert_eq!(material.diffuse(), Some(red));
+    assert_eq!(material.roughness(), Some(0.25));
+    assert_eq!(material.map(PropertyKind::Specular), Some(texture));
+    assert_eq!(material.illumination_model(), Some(2));
+    assert_eq!(
+        material.properties().collect::<Vec<_>>(),
+        [
+            MaterialProperty::DiffuseColor(red),
+            MaterialProperty::Roughness(0.25),
+            MaterialProperty::SpecularMap(texture),
+            MaterialProperty::IlluminationModel(2),
+        ]
+    );
+
+    // A mapped property has no scalar value, and a scalar one no map.
+    assert_eq!(material.specular(), None);
+    assert_eq!(material.map(PropertyKind::Diffuse), None);
+    assert_eq!(material.map(PropertyKind::Roughness), None);
+    assert_eq!(material.map_il(), None);
+    assert_eq!(material.metallic(), None);
+}
+
+#[test]
+fn values_have_to_fit_the_property() {
+    let mut b = ModelBuilder::new();
+    let material = b.add_material("plain").unwrap();
+    for (kind, value) in [
+        (PropertyKind::Diffuse, PropertyValue::Float(1.0)),
+        (PropertyKind::Roughness, PropertyValue::Color(Color(0))),
+        (PropertyKind::IlluminationModel, PropertyValue::Int(256)),
+    ] {
+        let err = b.set_material_property(material, kind, value).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnkownProperty);
+    }
+    let err = b
+        .set_material_property(material, PropertyKind::Diffuse, PropertyValue::Texture(0))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownImg);
+}
@@ -0,0 +1,138 @@
+//! Converting materials to metallic-roughness.
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+use model3d::*;
+
+fn material(props: &[(PropertyKind, PropertyValue)], textures: usize) -> PbrMaterial {
+    let mut b = ModelBuilder::new();
+    for i in 0..textures {
+        b.add_texture(TextureData {
+            name: format!("map{}", i),
+            ..Default::default()
+        })
+        .unwrap();
+    }
+    let material = b.add_material("material").unwrap();
+    for &(kind, value) in props {
+        b.set_material_property(material, kind, value).unwrap();
+    }
+    b.build().unwrap().materials()[0].to_pbr()
+}
+
+fn assert_near(a: &[f32], b: &[f32], tolerance: f32) {
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(b) {
+        assert!((a - b).abs() <= tolerance, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn phong_materials_are_solved() {
+    use PropertyKind::*;
+
+    // A dielectric: no specular highlight, the diffuse color is the base color.
+    let pbr = material(
+        &[
+            (
+                Diffuse,
+                PropertyValue::Color(Color::from_linear([0.48, 0.24, 0.096, 1.0])),
+            ),
+            (
+                Specular,
+                PropertyValue::Color(Color::from_rgba8([0, 0, 0, 255])),
+            ),
+            (SpecularExponent, PropertyValue::Float(98.0)),
+        ],
+        0,
+    );
+    assert_eq!(pbr.metallic, 0.0);
+    assert_near(&pbr.base_color, &[0.5, 0.25, 0.1, 1.0], 0.01);
+    assert_near(&[pbr.roughness], &[0.02f32.sqrt()], 1e-6);
+    assert_eq!(pbr.alpha_mode, AlphaMode::Opaque);
+
+    // A metal: black diffuse and white specular.
+    let pbr = material(
+        &[
+            (
+                Diffuse,
+                PropertyValue::Color(Color::from_rgba8([0, 0, 0, 255])),
+            ),
+            (Specular, PropertyValue::Color(Color::from_rgba8([255; 4]))),
+        ],
+        0,
+    );
+    assert_near(&[pbr.metallic], &[1.0], 1e-6);
+    assert_near(&pbr.base_color, &[1.0; 4], 1e-6);
+    assert_eq!(pbr.roughness, 1.0);
+}
+
+#[test]
+fn pbr_properties_are_kept() {
+    use PropertyKind::*;
+
+    let pbr = material(
+        &[
+            (
+                Diffuse,
+                PropertyValue::Color(Color::from_rgba8([255, 0, 0, 255])),
+            ),
+            (Metallic, PropertyValue::Float(0.75)),
+            (Roughness, PropertyValue::Float(0.25)),
+            // Left alone, `Pm` and `Pr` take precedence.
+            (Specular, PropertyValue::Color(Color::from_rgba8([255; 4]))),
+            (SpecularExponent, PropertyValue::Float(10.0)),
+            (
+                Emissive,
+                PropertyValue::Color(Color::from_rgba8([0, 255, 0, 255])),
+            ),
+            (Dissolve, PropertyValue::Float(0.5)),
+            (RefractionIndex, PropertyValue::Float(1.33)),
+        ],
+        0,
+    );
+    assert_eq!(
+        pbr,
+        PbrMaterial {
+            base_color: [1.0, 0.0, 0.0, 0.5],
+            metallic: 0.75,
+            roughness: 0.25,
+            emissive: [0.0, 1.0, 0.0],
+            alpha_mode: AlphaMode::Blend,
+            ior: 1.33,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn maps_are_assigned() {
+    use PropertyKind::*;
+
+    let maps = [
+        Diffuse,
+        Roughness,
+        Metallic,
+        IlluminationModel,
+        Ambient,
+        Emissive,
+        Dissolve,
+    ];
+    let props: Vec<_> = (maps.iter().enumerate())
+        .map(|(i, &kind)| (kind, PropertyValue::Texture(i as u32)))
+        .collect();
+    let pbr = material(&props, maps.len());
+    assert_eq!(
+        pbr,
+        PbrMaterial {
+            base_color_map: Some(0),
+            roughness_map: Some(1),
+            metallic_map: Some(2),
+            normal_map: Some(3),
+            occlusion_map: Some(4),
+            emissive_map: Some(5),
+            // A dissolve map without a dissolve value is a cutout.
+            alpha_mode: AlphaMode::Mask(0.5),
+            ..Default::default()
+        }
+    );
+}
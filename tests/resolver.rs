@@ -0,0 +1,38 @@
+//! Finding external assets by name.
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+mod common;
+
+use common::*;
+use model3d::*;
+use std::collections::HashMap;
+
+#[test]
+fn dir_resolver_stays_inside_its_root() {
+    let dir = temp_dir("dir-resolver");
+    std::fs::create_dir(dir.join("textures")).unwrap();
+    std::fs::write(dir.join("textures").join("wood.png"), b"wood").unwrap();
+    std::fs::write(dir.join("secret"), b"secret").unwrap();
+
+    let resolver = DirResolver::new(dir.join("textures"));
+    assert_eq!(resolver.resolve("wood.png"), Some(b"wood".to_vec()));
+    assert_eq!(resolver.resolve("stone.png"), None);
+    assert_eq!(resolver.resolve("../secret"), None);
+    let absolute = dir.join("textures").join("wood.png");
+    assert_eq!(resolver.resolve(absolute.to_str().unwrap()), None);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn chain_falls_through_in_order() {
+    let first = HashMap::from([("wood".to_string(), b"first".to_vec())]);
+    let second = HashMap::from([
+        ("wood".to_string(), b"second".to_vec()),
+        ("stone".to_string(), b"second".to_vec()),
+    ]);
+    let chain = ChainResolver::new().with(&first).with(&second);
+    assert_eq!(chain.resolve("wood"), Some(b"first".to_vec()));
+    assert_eq!(chain.resolve("stone"), Some(b"second".to_vec()));
+    assert_eq!(chain.resolve("glass"), None);
+}
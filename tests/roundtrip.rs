@@ -0,0 +1,177 @@
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+mod common;
+
+use common::*;
+use model3d::*;
+
+fn roundtrip(options: SaveOptions) -> Model {
+    let saved = sample().save(options).unwrap();
+    let report = load(&saved).unwrap();
+    assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    report.model
+}
+
+#[test]
+fn every_compression_and_quantization() {
+    let expected = Model::from_data(&sample()).unwrap();
+    for compression in [Compression::Zlib, Compression::None] {
+        for (quantization, tolerance) in [
+            (QuantizeQuality::Int8, 1.0 / 127.0),
+            (QuantizeQuality::Int16, 1.0 / 32767.0),
+            (QuantizeQuality::F32, 0.0),
+            (QuantizeQuality::F64, 0.0),
+        ] {
+            let options = SaveOptions::new()
+                .compression(compression)
+                .quantization(quantization);
+            let saved = sample().save(options).unwrap();
+            let report = load(&saved).unwrap();
+            // Doubles are narrowed to single precision floats.
+            let truncated = quantization == QuantizeQuality::F64;
+            let warnings: Vec<_> = report.warnings.iter().map(|w| w.0.kind()).collect();
+            assert_eq!(
+                warnings,
+                if truncated {
+                    vec![ErrorKind::Truncating]
+                } else {
+                    vec![]
+                }
+            );
+
+            let model = report.model;
+            assert_eq!(model.faces().len(), 2);
+            assert_close(&corners(&model), &corners(&expected), tolerance);
+        }
+    }
+}
+
+#[test]
+fn keeps_everything_but_geometry() {
+    let model = roundtrip(SaveOptions::new());
+    assert_eq!(model.name(), "sample");
+    assert_eq!(model.author(), "model3d");
+    assert_eq!(model.license(), "MIT");
+
+    let material = &model.materials()[0];
+    assert_eq!(material.name(), "red");
+    assert_eq!(
+        material.diffuse(),
+        Some(Color::from_rgba8([200, 10, 10, 255]))
+    );
+    assert_eq!(material.roughness(), Some(0.5));
+    assert!(model.faces().iter().all(|f| f.materialid == 0));
+
+    let bones: Vec<_> = model.bones().iter().map(|b| b.name().to_string()).collect();
+    assert_eq!(bones, ["root", "tip"]);
+    assert_eq!(model.actions()[0].name(), "wave");
+    assert_eq!(model.actions()[0].frames().len(), 2);
+    assert_eq!(model.labels()[0].text(), "first corner");
+
+    assert_eq!(model.voxel_types()[0].name(), "stone");
+    assert_eq!(model.voxels()[0].size(), [2, 1, 2]);
+    assert_eq!(model.voxels()[0].get(1, 0, 1), Some(Voxel::Type(0)));
+}
+
+#[test]
+fn data_survives_from_data_and_to_data() {
+    let data = sample();
+    let model = Model::from_data(&data).unwrap();
+    let again = model.to_data();
+    assert_eq!(again.textures, data.textures);
+    assert_eq!(again.voxels, data.voxels);
+    assert_eq!(again.labels, data.labels);
+    assert_eq!(again.faces.len(), data.faces.len());
+    assert_eq!(
+        again.save(SaveOptions::new()).unwrap(),
+        data.save(SaveOptions::new()).unwrap()
+    );
+}
+
+#[test]
+fn stripped_parts_are_left_out() {
+    let model = roundtrip(SaveOptions::new().strip(Strip::BONES | Strip::MATERIALS));
+    assert!(model.bones().is_empty());
+    assert!(model.actions().is_empty());
+    assert!(model.materials().is_empty());
+    assert_eq!(model.faces().len(), 2);
+}
+
+#[test]
+fn texture_pixels_are_inlined_as_png() {
+    let mut data = sample();
+    let pixels: Vec<u8> = (0..2 * 3 * 4).map(|i| i as u8 * 10).collect();
+    data.textures.push(TextureData {
+        name: "checker".to_string(),
+        width: 2,
+        height: 3,
+        format: TextureFormat::RGBA,
+        pixels: pixels.clone(),
+    });
+    let mut prop = data.materials[0].props[0];
+    prop.type_ = 128;
+    prop.value.textureid = (data.textures.len() - 1) as _;
+    data.materials[0].props.push(prop);
+
+    // Without inlining only the name is saved, and nothing resolves it.
+    let saved = data.save(SaveOptions::new()).unwrap();
+    assert!(load(&saved).unwrap().model.textures().is_empty());
+
+    let saved = data.save(SaveOptions::new().inline_textures(true)).unwrap();
+    let model = load(&saved).unwrap().model;
+    let texture = &model.textures()[0];
+    assert_eq!(texture.name(), "checker");
+    assert_eq!((texture.width(), texture.height()), (2, 3));
+    assert_eq!(texture.format(), TextureFormat::RGBA);
+    assert_eq!(texture.data(), pixels);
+    assert_eq!(model.inlined_textures()[0].name(), "checker");
+
+    // Pixels which don't fill the texture can't be encoded.
+    data.textures.last_mut().unwrap().pixels.pop();
+    let err = data
+        .save(SaveOptions::new().inline_textures(true))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownImg);
+}
+
+struct Full;
+
+impl std::io::Write for Full {
+    fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::ErrorKind::WriteZero.into())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn saves_to_writers_and_files() {
+    let options = SaveOptions::new();
+    let expected = sample().save(options).unwrap();
+    let corners_of = |file: &[u8]| corners(&load(file).unwrap().model);
+
+    let mut written = Vec::new();
+    sample().save_to_writer(&mut written, options).unwrap();
+    assert_eq!(written, expected);
+    assert_eq!(corners_of(&written), corners_of(&expected));
+
+    let dir = temp_dir("save");
+    let path = dir.join("sample.m3d");
+    sample().save_to_file(&path, options).unwrap();
+    let model = Model::load(&path, LoadOptions::new()).unwrap().model;
+    assert_eq!(corners(&model), corners_of(&expected));
+
+    // The writer's error is kept, a missing directory is reported with the path.
+    let err = sample().save_to_writer(Full, options).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Io);
+    let io = err.io_error().unwrap();
+    assert_eq!(io.kind(), std::io::ErrorKind::WriteZero);
+    let missing = dir.join("missing").join("sample.m3d");
+    let err = sample().save_to_file(&missing, options).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FileNotFound);
+    assert_eq!(err.path(), Some(missing.as_path()));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,143 @@
+//! Meshing voxel blocks into triangles.
+#![cfg(not(any(feature = "double", feature = "small-index")))]
+
+use model3d::*;
+
+const STONE: u16 = 0;
+const CLEAR: u16 = 0xFFFE;
+
+fn block(position: [i32; 3], size: [u32; 3], data: Vec<u16>) -> VoxelBlockData {
+    VoxelBlockData {
+        name: String::new(),
+        x: position[0],
+        y: position[1],
+        z: position[2],
+        width: size[0],
+        height: size[1],
+        depth: size[2],
+        uncertain: 0,
+        group: 0,
+        data,
+    }
+}
+
+fn model(blocks: Vec<VoxelBlockData>) -> Model {
+    let mut data = ModelData::default();
+    data.voxel_types.push(VoxelTypeData {
+        name: "stone".to_string(),
+        rotation: 0,
+        shape: 0,
+        material_id: Index::MAX,
+        color: 0xFF808080,
+        skin_id: Index::MAX,
+        items: Vec::new(),
+    });
+    data.voxels = blocks;
+    Model::from_data(&data).unwrap()
+}
+
+fn mesh(blocks: Vec<VoxelBlockData>) -> VoxelMesh {
+    model(blocks).mesh_voxels()
+}
+
+fn position(mesh: &VoxelMesh, index: u32) -> [f32; 3] {
+    let v = &mesh.vertices[index as usize];
+    [v.x, v.y, v.z]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[test]
+fn voxels_are_indexed_by_position() {
+    // Rows run along x, then z, then y.
+    let model = model(vec![block([5, 6, 7], [2, 3, 4], (0..24).collect())]);
+    let block = &model.voxels()[0];
+    assert_eq!(block.get(0, 0, 0), Some(Voxel::Type(0)));
+    assert_eq!(block.get(1, 0, 0), Some(Voxel::Type(1)));
+    assert_eq!(block.get(0, 0, 1), Some(Voxel::Type(2)));
+    assert_eq!(block.get(0, 1, 0), Some(Voxel::Type(8)));
+    assert_eq!(block.get(1, 2, 3), Some(Voxel::Type(23)));
+}
+
+#[test]
+fn voxels_outside_the_block_are_none() {
+    let mut data = vec![CLEAR; 24];
+    data[0] = 0xFFFF;
+    let model = model(vec![block([0; 3], [2, 3, 4], data)]);
+    let block = &model.voxels()[0];
+    assert_eq!(block.get(0, 0, 0), Some(Voxel::Undefined));
+    assert_eq!(block.get(1, 2, 3), Some(Voxel::Clear));
+    for [x, y, z] in [[2, 0, 0], [0, 3, 0], [0, 0, 4], [u32::MAX; 3]] {
+        assert_eq!(block.get(x, y, z), None, "{} {} {}", x, y, z);
+    }
+}
+
+#[test]
+fn single_voxel_is_a_cube() {
+    let mesh = mesh(vec![block([0; 3], [1; 3], vec![STONE])]);
+    assert_eq!(mesh.faces.len(), 12);
+    // 8 corners and 6 normals.
+    assert_eq!(mesh.vertices.len(), 14);
+}
+
+#[test]
+fn neighbouring_faces_are_merged() {
+    let mesh = mesh(vec![block([0; 3], [2, 1, 1], vec![STONE; 2])]);
+    assert_eq!(mesh.faces.len(), 12);
+    let xs: Vec<f32> = mesh
+        .faces
+        .iter()
+        .flat_map(|f| f.vertex)
+        .map(|v| position(&mesh, v)[0])
+        .collect();
+    assert_eq!(xs.iter().cloned().fold(f32::MIN, f32::max), 2.0);
+}
+
+#[test]
+fn faces_between_blocks_are_culled() {
+    let mesh = mesh(vec![
+        block([0; 3], [1; 3], vec![STONE]),
+        block([1, 0, 0], [1; 3], vec![STONE]),
+    ]);
+    assert_eq!(mesh.faces.len(), 20);
+}
+
+#[test]
+fn later_blocks_win() {
+    // The second block clears the voxel the first one put there.
+    let mesh = mesh(vec![
+        block([0; 3], [2, 1, 1], vec![STONE; 2]),
+        block([1, 0, 0], [1; 3], vec![CLEAR]),
+    ]);
+    assert_eq!(mesh.faces.len(), 12);
+    let far = mesh.faces.iter().flat_map(|f| f.vertex);
+    assert!(far.map(|v| position(&mesh, v)[0]).all(|x| x <= 1.0));
+}
+
+#[test]
+fn faces_wind_outwards() {
+    let mesh = mesh(vec![block([0; 3], [2, 1, 3], vec![STONE; 6])]);
+    let center = [1.0, 0.5, 1.5];
+    for face in &mesh.faces {
+        let [a, b, c] = face.vertex.map(|v| position(&mesh, v));
+        let winding = cross(sub(b, a), sub(c, a));
+        let normal = position(&mesh, face.normal[0]);
+        assert!(dot(winding, normal) > 0.0, "{:?}", face);
+        let centroid = [0, 1, 2].map(|k| (a[k] + b[k] + c[k]) / 3.0);
+        assert!(dot(sub(centroid, center), normal) > 0.0, "{:?}", face);
+    }
+}